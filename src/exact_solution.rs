@@ -0,0 +1,26 @@
+use std::collections::HashMap;
+
+use num_rational::BigRational;
+
+use crate::{SolveStatus, Variable};
+
+// Represents the result of solving a linear program in exact rational arithmetic
+#[derive(Clone, Debug)]
+pub struct ExactSolution<'a> {
+    pub status: SolveStatus,
+    // Only given for optimal solutions
+    pub objective_value: Option<BigRational>,
+    // The last vertex visited when the program is unbounded, and empty when
+    // it is infeasible
+    pub values: HashMap<Variable<'a>, BigRational>,
+    // Per constraint in order, the rate at which the objective value changes
+    // as its right hand side grows. Only given for optimal solutions
+    pub shadow_prices: Vec<BigRational>,
+    pub iterations: usize,
+}
+
+impl<'a> ExactSolution<'a> {
+    pub fn value(&self, variable: &Variable<'a>) -> Option<&BigRational> {
+        self.values.get(variable)
+    }
+}
@@ -1,15 +1,42 @@
 mod simplex_method_solver;
+mod exact_solution;
 mod lp_structs;
+mod mip_solution;
+mod pivot_rule;
+mod sensitivity;
+mod solution;
+mod solve_error;
+mod solver_options;
 
 pub use lp_structs::{
+    Coefficient,
     Constraint,
+    Evaluable,
+    EvaluationContext,
+    EvaluationError,
     Expression,
     LinearProgram,
+    MixedIntegerProgram,
     Objective,
     Operator,
+    RationalCoefficient,
     Substitutable,
     Term,
     Variable,
+    VariableContainer,
 };
 
-pub use simplex_method_solver::simplex_method_solver;
\ No newline at end of file
+pub use exact_solution::ExactSolution;
+pub use mip_solution::{MipProgress, MipSolution};
+pub use num_rational::BigRational;
+pub use pivot_rule::PivotRule;
+pub use sensitivity::{SensitivityRange, SensitivityReport};
+pub use solution::{Basis, Phase, Pivot, Solution, SolveStatus};
+pub use solve_error::SolveError;
+pub use solver_options::{Algorithm, InitialBasis, NodeSelection, SimplexMethod, SolverOptions};
+
+pub use simplex_method_solver::{
+    branch_and_bound_solver, branch_and_bound_solver_with_options, cutting_plane_solver,
+    cutting_plane_solver_with_options, exact_simplex_method_solver, find_irreducible_infeasible_subset,
+    simplex_method_solver, simplex_method_solver_from_basis, simplex_method_solver_with_options,
+};
\ No newline at end of file
@@ -0,0 +1,277 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+
+use super::{Constraint, Expression, Term, Variable};
+
+// Represents the numbers the coefficients and constants of a program can be
+// made of. Any type with the arithmetic the model is built with, a zero and a
+// one is one, such as f64, f32 and BigRational, as well as user types such as
+// double-double or interval numbers
+pub trait Coefficient:
+    Clone
+    + Debug
+    + Display
+    + PartialEq
+    + PartialOrd
+    + Zero
+    + One
+    + Neg<Output = Self>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+}
+
+impl<T> Coefficient for T where
+    T: Clone
+        + Debug
+        + Display
+        + PartialEq
+        + PartialOrd
+        + Zero
+        + One
+        + Neg<Output = T>
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + AddAssign
+        + SubAssign
+        + MulAssign
+        + DivAssign
+{
+}
+
+// How many units in the last place a coefficient may be from the fraction it
+// is read as, which covers the round-off of building it up from a few others
+const ROUNDING_ULPS: f64 = 4.0;
+
+// Represents the coefficients of a program that can be solved exactly, which
+// are read as fractions
+pub trait RationalCoefficient: Coefficient {
+    fn is_finite(&self) -> bool;
+
+    fn to_rational(&self) -> BigRational;
+}
+
+impl RationalCoefficient for f64 {
+    fn is_finite(&self) -> bool {
+        f64::is_finite(*self)
+    }
+
+    fn to_rational(&self) -> BigRational {
+        round_to_rational(*self)
+    }
+}
+
+impl RationalCoefficient for BigRational {
+    fn is_finite(&self) -> bool {
+        true
+    }
+
+    fn to_rational(&self) -> BigRational {
+        self.clone()
+    }
+}
+
+// Reads a coefficient as the simplest fraction within a few units in the last
+// place of it, which undoes the round-off of building the program in floating
+// point, such as 0.1 + 0.2 giving 0.30000000000000004, and reads a third
+// entered as 1.0 / 3.0 as exactly a third
+fn round_to_rational(value: f64) -> BigRational {
+    let exact = BigRational::from_float(value).expect("coefficients are finite");
+    if exact.is_integer() {
+        return exact;
+    }
+    let tolerance = BigRational::from_float(value.abs() * ROUNDING_ULPS * f64::EPSILON)
+        .expect("coefficients are finite");
+    let magnitude = exact.abs();
+    let simplest = simplest_between(&magnitude - &tolerance, &magnitude + &tolerance);
+    if value < 0.0 {
+        -simplest
+    } else {
+        simplest
+    }
+}
+
+// The fraction with the smallest denominator between two positive bounds,
+// which is an integer when one lies between them and otherwise shares its
+// integer part with both and has the simplest fraction between the
+// reciprocals of what is left of them as the reciprocal of its own
+fn simplest_between(lower: BigRational, upper: BigRational) -> BigRational {
+    let ceiling = lower.ceil();
+    if ceiling <= upper {
+        return ceiling;
+    }
+    let floor = lower.floor();
+    let rest = simplest_between((&upper - &floor).recip(), (&lower - &floor).recip());
+    floor + rest.recip()
+}
+
+// A number can only go on the left of a variable, term, expression or
+// constraint for the types listed here, as implementing the operators for any
+// coefficient on the left would implement them for a type parameter. Other
+// types such as f32 go on the right. A second float type here would leave
+// float literals such as the 2.0 in (2.0 * x).into() ambiguous
+macro_rules! scalar_on_the_left {
+    ($($scalar:ty),*) => {$(
+        impl<'a> Mul<Variable<'a>> for $scalar {
+            type Output = Term<'a, $scalar>;
+
+            fn mul(self, rhs: Variable<'a>) -> Self::Output {
+                Term::new(self, rhs)
+            }
+        }
+
+        impl<'a> Mul<&Variable<'a>> for $scalar {
+            type Output = Term<'a, $scalar>;
+
+            fn mul(self, rhs: &Variable<'a>) -> Self::Output {
+                Term::new(self, *rhs)
+            }
+        }
+
+        impl<'a> Mul<Term<'a, $scalar>> for $scalar {
+            type Output = Term<'a, $scalar>;
+
+            fn mul(self, rhs: Term<'a, $scalar>) -> Self::Output {
+                rhs * self
+            }
+        }
+
+        impl<'a> Mul<&Term<'a, $scalar>> for $scalar {
+            type Output = Term<'a, $scalar>;
+
+            fn mul(self, rhs: &Term<'a, $scalar>) -> Self::Output {
+                rhs * self
+            }
+        }
+
+        impl<'a> Add<Term<'a, $scalar>> for $scalar {
+            type Output = Expression<'a, $scalar>;
+
+            fn add(self, rhs: Term<'a, $scalar>) -> Self::Output {
+                rhs + self
+            }
+        }
+
+        impl<'a> Add<&Term<'a, $scalar>> for $scalar {
+            type Output = Expression<'a, $scalar>;
+
+            fn add(self, rhs: &Term<'a, $scalar>) -> Self::Output {
+                rhs + self
+            }
+        }
+
+        impl<'a> Add<Expression<'a, $scalar>> for $scalar {
+            type Output = Expression<'a, $scalar>;
+
+            fn add(self, rhs: Expression<'a, $scalar>) -> Self::Output {
+                rhs + self
+            }
+        }
+
+        impl<'a> Add<&Expression<'a, $scalar>> for $scalar {
+            type Output = Expression<'a, $scalar>;
+
+            fn add(self, rhs: &Expression<'a, $scalar>) -> Self::Output {
+                rhs + self
+            }
+        }
+
+        impl<'a> Sub<Expression<'a, $scalar>> for $scalar {
+            type Output = Expression<'a, $scalar>;
+
+            fn sub(self, rhs: Expression<'a, $scalar>) -> Self::Output {
+                rhs - self
+            }
+        }
+
+        impl<'a> Sub<&Expression<'a, $scalar>> for $scalar {
+            type Output = Expression<'a, $scalar>;
+
+            fn sub(self, rhs: &Expression<'a, $scalar>) -> Self::Output {
+                rhs - self
+            }
+        }
+
+        impl<'a> Mul<Expression<'a, $scalar>> for $scalar {
+            type Output = Expression<'a, $scalar>;
+
+            fn mul(self, rhs: Expression<'a, $scalar>) -> Self::Output {
+                rhs * self
+            }
+        }
+
+        impl<'a> Mul<&Expression<'a, $scalar>> for $scalar {
+            type Output = Expression<'a, $scalar>;
+
+            fn mul(self, rhs: &Expression<'a, $scalar>) -> Self::Output {
+                rhs * self
+            }
+        }
+
+        impl<'a> Mul<Constraint<'a, $scalar>> for $scalar {
+            type Output = Constraint<'a, $scalar>;
+
+            fn mul(self, rhs: Constraint<'a, $scalar>) -> Self::Output {
+                rhs * self
+            }
+        }
+
+        impl<'a> Mul<&Constraint<'a, $scalar>> for $scalar {
+            type Output = Constraint<'a, $scalar>;
+
+            fn mul(self, rhs: &Constraint<'a, $scalar>) -> Self::Output {
+                rhs * self
+            }
+        }
+    )*};
+}
+
+scalar_on_the_left!(f64, BigRational);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Operator;
+
+    #[test]
+    fn test_builds_models_over_other_numbers() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let expr = (x * -3.0f32) + (y * 1.5f32) + 2.0f32;
+        assert_eq!(format!("{}", expr), "1.5y - 3x + 2");
+
+        // thirds add up to exactly one, where 1.0 / 3.0 + 2.0 / 3.0 would not
+        // have to in floating point
+        let third = BigRational::new(1.into(), 3.into());
+        let expr = (third.clone() * x) + (BigRational::from_integer(2.into()) * third.clone() * x) - third.clone();
+        assert_eq!(expr.terms, vec![Term::new(BigRational::one(), x)]);
+        assert_eq!(format!("{}", expr), "x - 1/3");
+
+        let constraint = Constraint { lhs: expr, operator: Operator::LTE, rhs: Expression::zero() + BigRational::one() };
+        let constraint = -third * constraint;
+        assert_eq!(constraint.operator, Operator::GTE);
+        assert_eq!(format!("{}", constraint), "-1/3x + 1/9 >= -1/3");
+    }
+
+    #[test]
+    fn test_reads_coefficients_as_the_fractions_they_round() {
+        let fraction = |numerator: i64, denominator: i64| BigRational::new(numerator.into(), denominator.into());
+        assert_eq!((0.1 + 0.2).to_rational(), fraction(3, 10));
+        assert_eq!((1.0 / 3.0).to_rational(), fraction(1, 3));
+        assert_eq!((-2.0 / 7.0).to_rational(), fraction(-2, 7));
+        assert_eq!(12.0.to_rational(), fraction(12, 1));
+    }
+}
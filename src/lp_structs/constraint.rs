@@ -4,6 +4,9 @@ use std::ops::DivAssign;
 use std::ops::Mul;
 use std::ops::MulAssign;
 
+use itertools::Itertools;
+
+use super::Coefficient;
 use super::Expression;
 use super::Operator;
 use super::Substitutable;
@@ -13,107 +16,91 @@ use super::Variable as V;
 // Represents a constraint in the linear program
 
 #[derive(Clone, Debug)]
-pub struct Constraint<'a> {
-    pub lhs: Expression<'a>,
+pub struct Constraint<'a, T = f64> {
+    pub lhs: Expression<'a, T>,
     pub operator: Operator,
-    pub rhs: Expression<'a>,
+    pub rhs: Expression<'a, T>,
 }
 
-impl Display for Constraint<'_> {
+impl<T: Coefficient> Display for Constraint<'_, T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{} {} {}", self.lhs, self.operator, self.rhs)
     }
 }
 
-impl<'a> Substitutable<'a> for Constraint<'a> {
-    fn substitute(&mut self, var: &crate::Variable<'a>, expr: &Expression<'a>) {
+impl<'a, T: Coefficient> Substitutable<'a, T> for Constraint<'a, T> {
+    fn substitute(&mut self, var: &crate::Variable<'a>, expr: &Expression<'a, T>) {
         self.lhs.substitute(var, expr);
         self.rhs.substitute(var, expr);
     }
 }
 
-impl<'a> VariableContainer<'a> for Constraint<'a> {
+impl<'a, T> VariableContainer<'a> for Constraint<'a, T> {
     type I = std::vec::IntoIter<V<'a>>;
 
     fn variables_iter(&self) -> Self::I {
-        let mut vars = self.lhs.variables_iter()
+        let vars = self.lhs.variables_iter()
             .chain(self.rhs.variables_iter())
+            .unique()
             .collect::<Vec<_>>();
-        vars.dedup();
         vars.into_iter()
     }
 }
 
-impl<'a> Mul<f64> for Constraint<'a> {
-    type Output = Constraint<'a>;
+impl<'a, T: Coefficient> Mul<T> for Constraint<'a, T> {
+    type Output = Constraint<'a, T>;
 
-    fn mul(mut self, rhs: f64) -> Self::Output {
+    fn mul(mut self, rhs: T) -> Self::Output {
         self *= rhs;
         self
     }
 }
 
-impl<'a> Mul<Constraint<'a>> for f64 {
-    type Output = Constraint<'a>;
-
-    fn mul(self, rhs: Constraint<'a>) -> Self::Output {
-        rhs * self
-    }
-}
-
-impl<'a> Mul<f64> for &Constraint<'a> {
-    type Output = Constraint<'a>;
+impl<'a, T: Coefficient> Mul<T> for &Constraint<'a, T> {
+    type Output = Constraint<'a, T>;
 
-    fn mul(self, rhs: f64) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         let mut c = self.clone();
         c *= rhs;
         c
     }
 }
 
-impl<'a> Mul<&Constraint<'a>> for f64 {
-    type Output = Constraint<'a>;
-
-    fn mul(self, rhs: &Constraint<'a>) -> Self::Output {
-        rhs * self
-    }
-}
-
-impl<'a> Div<f64> for Constraint<'a> {
-    type Output = Constraint<'a>;
+impl<'a, T: Coefficient> Div<T> for Constraint<'a, T> {
+    type Output = Constraint<'a, T>;
 
-    fn div(mut self, rhs: f64) -> Self::Output {
+    fn div(mut self, rhs: T) -> Self::Output {
         self /= rhs;
         self
     }
 }
 
-impl<'a> Div<f64> for &Constraint<'a> {
-    type Output = Constraint<'a>;
+impl<'a, T: Coefficient> Div<T> for &Constraint<'a, T> {
+    type Output = Constraint<'a, T>;
 
-    fn div(self, rhs: f64) -> Self::Output {
+    fn div(self, rhs: T) -> Self::Output {
         let mut c = self.clone();
         c /= rhs;
         c
     }
 }
 
-impl<'a> MulAssign<f64> for Constraint<'a> {
-    fn mul_assign(&mut self, rhs: f64) {
-        if rhs < 0.0 {
+impl<'a, T: Coefficient> MulAssign<T> for Constraint<'a, T> {
+    fn mul_assign(&mut self, rhs: T) {
+        if rhs < T::zero() {
             self.operator = self.operator.flip();
         }
-        self.lhs *=rhs;
+        self.lhs *=rhs.clone();
         self.rhs *= rhs;
     }
 }
 
-impl<'a> DivAssign<f64> for Constraint<'a> {
-    fn div_assign(&mut self, rhs: f64) {
-        if rhs < 0.0 {
+impl<'a, T: Coefficient> DivAssign<T> for Constraint<'a, T> {
+    fn div_assign(&mut self, rhs: T) {
+        if rhs < T::zero() {
             self.operator = self.operator.flip();
         }
-        self.lhs /=rhs;
+        self.lhs /=rhs.clone();
         self.rhs /= rhs;
     }
 }
\ No newline at end of file
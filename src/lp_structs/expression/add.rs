@@ -1,13 +1,13 @@
-use super::super::Term;
+use super::super::{Coefficient, Term};
 
 use super::Expression;
 
 use std::ops::Add;
 
-impl<'a> Add<&Expression<'a>> for &Expression<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Add<&Expression<'a, T>> for &Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn add(self, rhs: &Expression<'a>) -> Expression<'a> {
+    fn add(self, rhs: &Expression<'a, T>) -> Expression<'a, T> {
         let mut expr = self.clone();
         expr.terms.extend(rhs.terms.clone());
         expr.consolidate();
@@ -15,74 +15,58 @@ impl<'a> Add<&Expression<'a>> for &Expression<'a> {
     }
 }
 
-impl<'a> Add<&Term<'a>> for &Expression<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Add<&Term<'a, T>> for &Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn add(self, rhs: &Term<'a>) -> Expression<'a> {
+    fn add(self, rhs: &Term<'a, T>) -> Expression<'a, T> {
         let mut expr = self.clone();
-        expr.terms.push(*rhs);
+        expr.terms.push(rhs.clone());
         expr.consolidate();
         expr
     }
 }
 
-impl<'a> Add<&Expression<'a>> for &Term<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Add<&Expression<'a, T>> for &Term<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn add(self, rhs: &Expression<'a>) -> Expression<'a> {
+    fn add(self, rhs: &Expression<'a, T>) -> Expression<'a, T> {
         rhs + self
     }
 }
 
-impl<'a> Add<Term<'a>> for Expression<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Add<Term<'a, T>> for Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn add(mut self, rhs: Term<'a>) -> Expression {
+    fn add(mut self, rhs: Term<'a, T>) -> Expression<'a, T> {
         self.terms.push(rhs);
         self.consolidate();
         self
     }
 }
 
-impl<'a> Add<Expression<'a>> for Term<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Add<Expression<'a, T>> for Term<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn add(self, rhs: Expression<'a>) -> Expression {
+    fn add(self, rhs: Expression<'a, T>) -> Expression<'a, T> {
         rhs + self
     }
 }
 
-impl<'a> Add<f64> for &Expression<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Add<T> for &Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn add(self, rhs: f64) -> Expression<'a> {
+    fn add(self, rhs: T) -> Expression<'a, T> {
         let mut expr = self.clone();
         expr.constant += rhs;
         expr
     }
 }
 
-impl<'a> Add<&Expression<'a>> for f64 {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Add<T> for Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn add(self, rhs: &Expression<'a>) -> Expression<'a> {
-        rhs + self
-    }
-}
-
-impl<'a> Add<f64> for Expression<'a> {
-    type Output = Expression<'a>;
-
-    fn add(mut self, rhs: f64) -> Expression<'a> {
+    fn add(mut self, rhs: T) -> Expression<'a, T> {
         self.constant += rhs;
         self
     }
 }
-
-impl<'a> Add<Expression<'a>> for f64 {
-    type Output = Expression<'a>;
-
-    fn add(self, rhs: Expression<'a>) -> Expression<'a> {
-        rhs + self
-    }
-}
@@ -1,41 +1,41 @@
-use super::super::Term;
+use super::super::{Coefficient, Term};
 
 use super::Expression;
 
 use std::ops::AddAssign;
 
-impl<'a> AddAssign<Expression<'a>> for Expression<'a> {
-    fn add_assign(&mut self, rhs: Expression<'a>) {
+impl<'a, T: Coefficient> AddAssign<Expression<'a, T>> for Expression<'a, T> {
+    fn add_assign(&mut self, rhs: Expression<'a, T>) {
         self.terms.extend(rhs.terms);
         self.constant += rhs.constant;
         self.consolidate();
     }
 }
 
-impl<'a> AddAssign<&Expression<'a>> for Expression<'a> {
-    fn add_assign(&mut self, rhs: &Expression<'a>) {
+impl<'a, T: Coefficient> AddAssign<&Expression<'a, T>> for Expression<'a, T> {
+    fn add_assign(&mut self, rhs: &Expression<'a, T>) {
         self.terms.extend(rhs.terms.clone());
-        self.constant += rhs.constant;
+        self.constant += rhs.constant.clone();
         self.consolidate();
     }
 }
 
-impl<'a> AddAssign<Term<'a>> for Expression<'a> {
-    fn add_assign(&mut self, rhs: Term<'a>) {
+impl<'a, T: Coefficient> AddAssign<Term<'a, T>> for Expression<'a, T> {
+    fn add_assign(&mut self, rhs: Term<'a, T>) {
         self.terms.push(rhs);
         self.consolidate();
     }
 }
 
-impl<'a> AddAssign<&Term<'a>> for Expression<'a> {
-    fn add_assign(&mut self, rhs: &Term<'a>) {
-        self.terms.push(*rhs);
+impl<'a, T: Coefficient> AddAssign<&Term<'a, T>> for Expression<'a, T> {
+    fn add_assign(&mut self, rhs: &Term<'a, T>) {
+        self.terms.push(rhs.clone());
         self.consolidate();
     }
 }
 
-impl AddAssign<f64> for Expression<'_> {
-    fn add_assign(&mut self, rhs: f64) {
+impl<T: Coefficient> AddAssign<T> for Expression<'_, T> {
+    fn add_assign(&mut self, rhs: T) {
         self.constant += rhs;
     }
 }
@@ -1,31 +1,32 @@
+use super::super::Coefficient;
 use super::{Expression, Term};
 
 use std::ops::Div;
 
-impl<'a> Div<f64> for &Expression<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Div<T> for &Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn div(self, rhs: f64) -> Self::Output {
+    fn div(self, rhs: T) -> Self::Output {
         Expression::new(
             &self
                 .terms
                 .iter()
-                .map(|term| term / rhs)
-                .collect::<Vec<Term>>(),
-            self.constant / rhs,
+                .map(|term| term / rhs.clone())
+                .collect::<Vec<Term<T>>>(),
+            self.constant.clone() / rhs,
         )
     }
 }
 
-impl<'a> Div<f64> for Expression<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Div<T> for Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn div(mut self, rhs: f64) -> Self::Output {
+    fn div(mut self, rhs: T) -> Self::Output {
         self.terms =
             self
                 .terms
                 .iter()
-                .map(|term| term / rhs)
+                .map(|term| term / rhs.clone())
                 .collect();
         self.constant /= rhs;
         self
@@ -1,12 +1,13 @@
+use super::super::Coefficient;
 use super::Expression;
 use std::ops::DivAssign;
 
-impl DivAssign<f64> for Expression<'_> {
-    fn div_assign(&mut self, rhs: f64) {
-        self.constant /= rhs;
+impl<T: Coefficient> DivAssign<T> for Expression<'_, T> {
+    fn div_assign(&mut self, rhs: T) {
         for term in self.terms.iter_mut() {
-            term.coefficient /= rhs;
+            term.coefficient /= rhs.clone();
         }
+        self.constant /= rhs;
     }
 }
 
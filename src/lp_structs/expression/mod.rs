@@ -1,23 +1,23 @@
 use std::{
+    cmp::Ordering,
+    collections::HashMap,
     fmt::Display,
 };
 
-use itertools::Itertools;
-
 use super::traits::RecursionGuard;
-use super::{Substitutable, VariableContainer, Variable as V};
+use super::{Coefficient, Substitutable, VariableContainer, Variable as V};
 
 use super::Term;
 
 // Represents an expression in the linear program
 #[derive(Clone, Debug, PartialEq)]
-pub struct Expression<'a> {
-    pub terms: Vec<Term<'a>>,
-    pub constant: f64,
+pub struct Expression<'a, T = f64> {
+    pub terms: Vec<Term<'a, T>>,
+    pub constant: T,
 }
 
-impl Expression<'_> {
-    pub fn new<'a>(terms: &[Term<'a>], constant: f64) -> Expression<'a> {
+impl<'a, T: Coefficient> Expression<'a, T> {
+    pub fn new(terms: &[Term<'a, T>], constant: T) -> Expression<'a, T> {
         let mut exp = Expression {
             terms: terms.to_vec(),
             constant,
@@ -26,34 +26,36 @@ impl Expression<'_> {
         exp
     }
 
-    pub fn zero() -> Expression<'static> {
+    pub fn zero() -> Expression<'a, T> {
         Expression {
             terms: vec![],
-            constant: 0.0,
+            constant: T::zero(),
         }
     }
 
+    // Merges all terms sharing a variable into a single term and drops
+    // any terms whose coefficients cancel out to zero
     pub fn consolidate(&mut self) {
-        self.terms = self
-            .terms
-            .iter()
-            .group_by(|term| term.variable)
-            .into_iter()
-            .map(|(v, group)| {
-                let coefficient = group.map(|x| x.coefficient).sum();
-                Term {
-                    variable: v,
-                    coefficient,
+        let mut positions: HashMap<V, usize> = HashMap::new();
+        let mut terms: Vec<Term<T>> = Vec::with_capacity(self.terms.len());
+        for term in &self.terms {
+            match positions.get(&term.variable) {
+                Some(&index) => terms[index].coefficient += term.coefficient.clone(),
+                None => {
+                    positions.insert(term.variable, terms.len());
+                    terms.push(term.clone());
                 }
-            })
-            .collect::<Vec<Term>>();
-        self.terms
-            .sort_by(|a, b| b.coefficient.partial_cmp(&a.coefficient).unwrap());
+            }
+        }
+        terms.retain(|term| !term.coefficient.is_zero());
+        // coefficients such as intervals that are not ordered keep their order
+        terms.sort_by(|a, b| b.coefficient.partial_cmp(&a.coefficient).unwrap_or(Ordering::Equal));
+        self.terms = terms;
     }
 }
 
-impl<'a> Substitutable<'a> for Expression<'a> {
-    fn substitute(&mut self, variable: &V<'a>, expression: &Expression<'a>) {
+impl<'a, T: Coefficient> Substitutable<'a, T> for Expression<'a, T> {
+    fn substitute(&mut self, variable: &V<'a>, expression: &Expression<'a, T>) {
         self.terms = self
             .terms
             .iter()
@@ -64,11 +66,11 @@ impl<'a> Substitutable<'a> for Expression<'a> {
                         .iter()
                         .map(|term| Term {
                             variable: term.variable,
-                            coefficient: term.coefficient * sub_term.coefficient,
+                            coefficient: term.coefficient.clone() * sub_term.coefficient.clone(),
                         })
                         .collect()
                 } else {
-                    vec![*sub_term]
+                    vec![sub_term.clone()]
                 }
             })
             .collect();
@@ -76,7 +78,7 @@ impl<'a> Substitutable<'a> for Expression<'a> {
     }
 }
 
-impl<'a> VariableContainer<'a> for Expression<'a> {
+impl<'a, T> VariableContainer<'a> for Expression<'a, T> {
     type I = std::vec::IntoIter<crate::Variable<'a>>;
 
     fn variables_iter(&self) -> Self::I {
@@ -86,103 +88,110 @@ impl<'a> VariableContainer<'a> for Expression<'a> {
     }
 }
 
-impl Display for Expression<'_> {
+// The size of a coefficient, without its sign
+fn magnitude<T: Coefficient>(value: &T) -> T {
+    if *value < T::zero() {
+        -value.clone()
+    } else {
+        value.clone()
+    }
+}
+
+impl<T: Coefficient> Display for Expression<'_, T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         let mut first = true;
         for term in &self.terms {
-            if term.coefficient == 0.0 {
+            if term.coefficient.is_zero() {
                 continue;
             }
-            if !first && term.coefficient > 0.0 {
+            if !first && term.coefficient > T::zero() {
                 write!(f, " + ")?;
             }
-            if term.coefficient < 0.0 {
+            if term.coefficient < T::zero() {
                 if first {
                     write!(f, "-")?;
                 } else {
                     write!(f, " - ")?;
                 }
             }
-            if term.coefficient.abs() != 1.0 {
-                write!(f, "{}", term.coefficient.abs())?;
+            if !magnitude(&term.coefficient).is_one() {
+                write!(f, "{}", magnitude(&term.coefficient))?;
             }
             write!(f, "{}", term.variable)?;
 
             first = false;
         }
-        if self.constant == 0.0 {
+        if self.constant.is_zero() {
             return Ok(());
-        } else if self.constant > 0.0 {
+        } else if self.constant > T::zero() {
             if first {
                 write!(f, "{}", self.constant)?;
             } else {
                 write!(f, " + {}", self.constant)?;
             }
         } else if first {
-            write!(f, "-{}", self.constant.abs())?;
+            write!(f, "-{}", magnitude(&self.constant))?;
         } else {
-            write!(f, " - {}", self.constant.abs())?;
+            write!(f, " - {}", magnitude(&self.constant))?;
         }
         Ok(())
     }
 }
 
-impl<'a> From<Term<'a>> for Expression<'a> {
-    fn from(term: Term<'a>) -> Expression<'a> {
+impl<'a, T: Coefficient> From<Term<'a, T>> for Expression<'a, T> {
+    fn from(term: Term<'a, T>) -> Expression<'a, T> {
         Expression {
             terms: vec![term],
-            constant: 0.0,
+            constant: T::zero(),
         }
     }
 }
 
-impl<'a, X> From<X> for Expression<'a>
+impl<'a, T: Coefficient, X> From<X> for Expression<'a, T>
 where
-    X: IntoIterator<Item = Term<'a>>
+    X: IntoIterator<Item = Term<'a, T>>
 {
-    fn from(terms: X) -> Expression<'a> {
+    fn from(terms: X) -> Expression<'a, T> {
         Expression {
             terms: terms.into_iter().collect(),
-            constant: 0.0,
+            constant: T::zero(),
         }
     }
 }
 
 
-impl<'a> From<V<'a>> for Expression<'a> {
-    fn from(variable: V<'a>) -> Expression<'a> {
+impl<'a, T: Coefficient> From<V<'a>> for Expression<'a, T> {
+    fn from(variable: V<'a>) -> Expression<'a, T> {
         Expression {
             terms: vec![variable.into()],
-            constant: 0.0,
+            constant: T::zero(),
         }
     }
 }
 
-impl<'a> super::Evaluable<'a> for Expression<'a> {
-    fn evaluate<X>(&self, source: &X) -> Result<f64, super::EvaluationError>
+impl<'a, T: Coefficient> super::Evaluable<'a, T> for Expression<'a, T> {
+    fn evaluate<X>(&self, source: &X) -> Result<T, super::EvaluationError>
     where
-        X: super::EvaluationContext<'a> {
+        X: super::EvaluationContext<'a, T> {
         self.terms
             .iter()
-            .map(|term| {
+            .try_fold(T::zero(), |sum, term| {
                 let value = source.evaluate(&term.variable)?;
-                Ok(term.coefficient * value)
+                Ok(sum + term.coefficient.clone() * value)
             })
-            .sum::<Result<f64, super::EvaluationError>>()
-            .map(|x| x + self.constant)
+            .map(|x| x + self.constant.clone())
     }
 
-    fn evaluate_with_recursion_guard<X>(&self, source: &X, guard: &RecursionGuard) -> Result<f64, super::EvaluationError>
+    fn evaluate_with_recursion_guard<X>(&self, source: &X, guard: &RecursionGuard) -> Result<T, super::EvaluationError>
     where
-        X: super::EvaluationContext<'a> {
+        X: super::EvaluationContext<'a, T> {
         self.terms
             .iter()
-            .map(|term| {
+            .try_fold(T::zero(), |sum, term| {
                 let value = source.evaluate_with_recursion_guard(&term.variable, guard)?;
-                Ok(term.coefficient * value)
+                Ok(sum + term.coefficient.clone() * value)
             })
-            .sum::<Result<f64, super::EvaluationError>>()
-            .map(|x| x + self.constant)
+            .map(|x| x + self.constant.clone())
     }
 }
 
@@ -1,49 +1,34 @@
+use super::super::Coefficient;
 use super::{Expression, Term};
 
 use std::ops::Mul;
 
-impl<'a> Mul<f64> for &Expression<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Mul<T> for &Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn mul(self, rhs: f64) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         Expression::new(
             &self
                 .terms
                 .iter()
-                .map(|term| term * rhs)
-                .collect::<Vec<Term>>(),
-            self.constant * rhs,
+                .map(|term| term * rhs.clone())
+                .collect::<Vec<Term<T>>>(),
+            self.constant.clone() * rhs,
         )
     }
 }
 
-impl<'a> Mul<&Expression<'a>> for f64 {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Mul<T> for Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn mul(self, rhs: &Expression<'a>) -> Self::Output {
-        rhs * self
-    }
-}
-
-impl<'a> Mul<f64> for Expression<'a> {
-    type Output = Expression<'a>;
-
-    fn mul(mut self, rhs: f64) -> Self::Output {
+    fn mul(mut self, rhs: T) -> Self::Output {
         self.terms =
             self
                 .terms
                 .iter()
-                .map(|term| term * rhs)
+                .map(|term| term * rhs.clone())
                 .collect();
         self.constant *= rhs;
         self
     }
 }
-
-impl<'a> Mul<Expression<'a>> for f64 {
-    type Output = Expression<'a>;
-
-    fn mul(self, rhs: Expression<'a>) -> Self::Output {
-        rhs * self
-    }
-}
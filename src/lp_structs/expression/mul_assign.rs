@@ -1,12 +1,13 @@
 use std::ops::MulAssign;
+use super::super::Coefficient;
 use super::Expression;
 
 
-impl MulAssign<f64> for Expression<'_> {
-    fn mul_assign(&mut self, rhs: f64) {
-        self.constant *= rhs;
+impl<T: Coefficient> MulAssign<T> for Expression<'_, T> {
+    fn mul_assign(&mut self, rhs: T) {
         for term in self.terms.iter_mut() {
-            term.coefficient *= rhs;
+            term.coefficient *= rhs.clone();
         }
+        self.constant *= rhs;
     }
 }
\ No newline at end of file
@@ -1,80 +1,64 @@
-use super::super::Term;
+use super::super::{Coefficient, Term};
 
 use super::Expression;
 
 use std::ops::Sub;
 
-impl<'a> Sub<&Expression<'a>> for &Expression<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Sub<&Expression<'a, T>> for &Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn sub(self, rhs: &Expression<'a>) -> Expression<'a> {
-        self + &(rhs * -1.0)
+    fn sub(self, rhs: &Expression<'a, T>) -> Expression<'a, T> {
+        self + &(rhs * -T::one())
     }
 }
 
-impl<'a> Sub<&Term<'a>> for &Expression<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Sub<&Term<'a, T>> for &Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn sub(self, rhs: &Term<'a>) -> Expression<'a> {
-        self + &(rhs * -1.0)
+    fn sub(self, rhs: &Term<'a, T>) -> Expression<'a, T> {
+        self + &(rhs * -T::one())
     }
 }
 
-impl<'a> Sub<&Expression<'a>> for &Term<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Sub<&Expression<'a, T>> for &Term<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn sub(self, rhs: &Expression<'a>) -> Self::Output {
+    fn sub(self, rhs: &Expression<'a, T>) -> Self::Output {
         rhs - self
     }
 }
 
-impl<'a> Sub<Term<'a>> for Expression<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Sub<Term<'a, T>> for Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn sub(self, rhs: Term<'a>) -> Self::Output {
-        self + (rhs * -1.0)
+    fn sub(self, rhs: Term<'a, T>) -> Self::Output {
+        self + (rhs * -T::one())
     }
 }
 
-impl<'a> Sub<Expression<'a>> for Term<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Sub<Expression<'a, T>> for Term<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn sub(self, rhs: Expression<'a>) -> Self::Output {
+    fn sub(self, rhs: Expression<'a, T>) -> Self::Output {
         rhs - self
     }
 }
 
-impl<'a> Sub<f64> for &Expression<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Sub<T> for &Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn sub(self, rhs: f64) -> Self::Output {
+    fn sub(self, rhs: T) -> Self::Output {
         let mut expr = self.clone();
         expr.constant -= rhs;
         expr
     }
 }
 
-impl<'a> Sub<f64> for Expression<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Sub<T> for Expression<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn sub(mut self, rhs: f64) -> Self::Output {
+    fn sub(mut self, rhs: T) -> Self::Output {
         self.constant -= rhs;
         self
     }
 }
-
-impl<'a> Sub<&Expression<'a>> for f64 {
-    type Output = Expression<'a>;
-
-    fn sub(self, rhs: &Expression<'a>) -> Self::Output {
-        rhs - self
-    }
-}
-
-impl<'a> Sub<Expression<'a>> for f64 {
-    type Output = Expression<'a>;
-
-    fn sub(self, rhs: Expression<'a>) -> Self::Output {
-        rhs - self
-    }
-}
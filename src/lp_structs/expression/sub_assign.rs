@@ -1,29 +1,29 @@
-use super::super::Term;
+use super::super::{Coefficient, Term};
 
 use super::Expression;
 
 use std::ops::SubAssign;
 
-impl<'a> SubAssign<&Expression<'a>> for Expression<'a> {
-    fn sub_assign(&mut self, rhs: &Expression<'a>) {
-        *self = &*self + &(rhs * -1.0);
+impl<'a, T: Coefficient> SubAssign<&Expression<'a, T>> for Expression<'a, T> {
+    fn sub_assign(&mut self, rhs: &Expression<'a, T>) {
+        *self = &*self + &(rhs * -T::one());
     }
 }
 
-impl<'a> SubAssign<Expression<'a>> for Expression<'a> {
-    fn sub_assign(&mut self, rhs: Expression<'a>) {
-        *self = &*self + &(rhs * -1.0);
+impl<'a, T: Coefficient> SubAssign<Expression<'a, T>> for Expression<'a, T> {
+    fn sub_assign(&mut self, rhs: Expression<'a, T>) {
+        *self = &*self + &(rhs * -T::one());
     }
 }
 
-impl<'a> SubAssign<&Term<'a>> for Expression<'a> {
-    fn sub_assign(&mut self, rhs: &Term<'a>) {
-        *self = &*self + &(rhs * -1.0)
+impl<'a, T: Coefficient> SubAssign<&Term<'a, T>> for Expression<'a, T> {
+    fn sub_assign(&mut self, rhs: &Term<'a, T>) {
+        *self = &*self + &(rhs * -T::one())
     }
 }
 
-impl<'a> SubAssign<f64> for Expression<'a> {
-    fn sub_assign(&mut self, rhs: f64) {
+impl<T: Coefficient> SubAssign<T> for Expression<'_, T> {
+    fn sub_assign(&mut self, rhs: T) {
         self.constant -= rhs;
     }
 }
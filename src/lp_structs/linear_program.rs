@@ -1,18 +1,22 @@
 use std::fmt::Display;
 
+use itertools::Itertools;
+
+use super::Coefficient;
 use super::Constraint;
 use super::Objective;
 use super::Variable as V;
 use super::VariableContainer;
 
 /// Represents a linear program with a vector of constraints
-/// and an objective function
-pub struct LinearProgram<'a> {
-    pub objective: Objective<'a>,
-    pub constraints: Vec<Constraint<'a>>,
+/// and an objective function, whose coefficients are f64 unless another
+/// numeric type is chosen
+pub struct LinearProgram<'a, T = f64> {
+    pub objective: Objective<'a, T>,
+    pub constraints: Vec<Constraint<'a, T>>,
 }
 
-impl Display for LinearProgram<'_> {
+impl<T: Coefficient> Display for LinearProgram<'_, T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "{}", self.objective)?;
         for constraint in &self.constraints {
@@ -22,14 +26,14 @@ impl Display for LinearProgram<'_> {
     }
 }
 
-impl<'a> VariableContainer<'a> for LinearProgram<'a> {
+impl<'a, T> VariableContainer<'a> for LinearProgram<'a, T> {
     type I = std::vec::IntoIter<V<'a>>;
 
     fn variables_iter(&self) -> Self::I {
-        let mut vars = self.objective.variables_iter()
+        let vars = self.objective.variables_iter()
             .chain(self.constraints.iter().flat_map(|c| c.variables_iter()))
+            .unique()
             .collect::<Vec<_>>();
-        vars.dedup();
         vars.into_iter()
     }
 }
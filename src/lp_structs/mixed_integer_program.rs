@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+use super::{Constraint, Expression, LinearProgram, Operator, Variable as V};
+
+// Represents a linear program in which some of the variables may only take
+// integer values
+pub struct MixedIntegerProgram<'a> {
+    // Marking a variable binary adds the constraints holding it between zero
+    // and one here
+    pub program: LinearProgram<'a>,
+    pub integer_variables: Vec<V<'a>>,
+}
+
+impl<'a> MixedIntegerProgram<'a> {
+    pub fn new(program: LinearProgram<'a>) -> Self {
+        MixedIntegerProgram {
+            program,
+            integer_variables: vec![],
+        }
+    }
+
+    pub fn set_integer(&mut self, variable: V<'a>) {
+        if !self.is_integer(&variable) {
+            self.integer_variables.push(variable);
+        }
+    }
+
+    pub fn set_binary(&mut self, variable: V<'a>) {
+        self.set_integer(variable);
+        self.program.constraints.push(Constraint {
+            lhs: variable.into(),
+            operator: Operator::GTE,
+            rhs: Expression::zero(),
+        });
+        self.program.constraints.push(Constraint {
+            lhs: variable.into(),
+            operator: Operator::LTE,
+            rhs: Expression::zero() + 1.0,
+        });
+    }
+
+    pub fn is_integer(&self, variable: &V<'a>) -> bool {
+        self.integer_variables.contains(variable)
+    }
+}
+
+impl Display for MixedIntegerProgram<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.program)?;
+        if !self.integer_variables.is_empty() {
+            let names = self.integer_variables.iter().map(|variable| variable.to_string()).collect::<Vec<_>>();
+            writeln!(f, "int: {}", names.join(", "))?;
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+mod coefficient;
 mod variable;
 mod term;
 mod expression;
@@ -6,8 +7,10 @@ mod constraint;
 mod objective;
 mod traits;
 mod linear_program;
+mod mixed_integer_program;
 
 pub use self::{
+    coefficient::{Coefficient, RationalCoefficient},
     variable::Variable,
     term::Term,
     expression::Expression,
@@ -23,4 +26,5 @@ pub use self::{
         RecursionGuard
     },
     linear_program::LinearProgram,
+    mixed_integer_program::MixedIntegerProgram,
 };
\ No newline at end of file
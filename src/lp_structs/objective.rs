@@ -1,19 +1,19 @@
 use std::fmt::{Error, Display, Formatter};
 
-use super::{Expression, Substitutable, VariableContainer, Variable as V};
+use super::{Coefficient, Expression, Substitutable, VariableContainer, Variable as V};
 
 /// Represents the objective function in a linear program
 /// The objective function is a vector of terms and a boolean
 /// indicating whether the objective is to be maximized
 /// or minimized
 #[derive(Clone, Debug)]
-pub struct Objective<'a> {
-    pub expression: Expression<'a>,
+pub struct Objective<'a, T = f64> {
+    pub expression: Expression<'a, T>,
     pub maximize: bool,
 }
 
-impl<'a> From<Expression<'a>> for Objective<'a> {
-    fn from(expression: Expression<'a>) -> Self {
+impl<'a, T> From<Expression<'a, T>> for Objective<'a, T> {
+    fn from(expression: Expression<'a, T>) -> Self {
         Objective {
             expression,
             maximize: true,
@@ -21,8 +21,8 @@ impl<'a> From<Expression<'a>> for Objective<'a> {
     }
 }
 
-impl<'a> Objective<'a> {
-    pub fn new(expression: Expression<'a>, maximize: bool) -> Self {
+impl<'a, T> Objective<'a, T> {
+    pub fn new(expression: Expression<'a, T>, maximize: bool) -> Self {
         Objective {
             expression,
             maximize,
@@ -30,7 +30,7 @@ impl<'a> Objective<'a> {
     }
 }
 
-impl Display for Objective<'_> {
+impl<T: Coefficient> Display for Objective<'_, T> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         if self.maximize {
             write!(f, "max: ")?;
@@ -41,13 +41,13 @@ impl Display for Objective<'_> {
     }
 }
 
-impl<'a> Substitutable<'a> for Objective<'a> {
-    fn substitute(&mut self, var: &crate::Variable<'a>, expr: &Expression<'a>) {
+impl<'a, T: Coefficient> Substitutable<'a, T> for Objective<'a, T> {
+    fn substitute(&mut self, var: &crate::Variable<'a>, expr: &Expression<'a, T>) {
         self.expression.substitute(var, expr);
     }
 }
 
-impl<'a> VariableContainer<'a> for Objective<'a> {
+impl<'a, T> VariableContainer<'a> for Objective<'a, T> {
     type I = std::vec::IntoIter<V<'a>>;
 
     fn variables_iter(&self) -> Self::I {
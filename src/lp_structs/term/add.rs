@@ -1,53 +1,37 @@
-use super::super::Expression;
+use super::super::{Coefficient, Expression};
 
 use super::Term;
 
 use std::ops::Add;
 
-impl<'a> Add<&Term<'a>> for &Term<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Add<&Term<'a, T>> for &Term<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn add(self, rhs: &Term<'a>) -> Self::Output {
-        Expression::new(&[*self, *rhs], 0.0)
+    fn add(self, rhs: &Term<'a, T>) -> Self::Output {
+        Expression::new(&[self.clone(), rhs.clone()], T::zero())
     }
 }
 
-impl<'a> Add<Term<'a>> for Term<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Add<Term<'a, T>> for Term<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn add(self, rhs: Term<'a>) -> Self::Output {
-        Expression::new(&[self, rhs], 0.0)
+    fn add(self, rhs: Term<'a, T>) -> Self::Output {
+        Expression::new(&[self, rhs], T::zero())
     }
 }
 
-impl<'a> Add<f64> for Term<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Add<T> for Term<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn add(self, rhs: f64) -> Self::Output {
+    fn add(self, rhs: T) -> Self::Output {
         Expression::new(&[self], rhs)
     }
 }
 
-impl<'a> Add<f64> for &Term<'a> {
-    type Output = Expression<'a>;
+impl<'a, T: Coefficient> Add<T> for &Term<'a, T> {
+    type Output = Expression<'a, T>;
 
-    fn add(self, rhs: f64) -> Self::Output {
-        Expression::new(&[*self], rhs)
+    fn add(self, rhs: T) -> Self::Output {
+        Expression::new(std::slice::from_ref(self), rhs)
     }
 }
-
-impl<'a> Add<Term<'a>> for f64 {
-    type Output = Expression<'a>;
-
-    fn add(self, rhs: Term<'a>) -> Self::Output {
-        rhs + self
-    }
-}
-
-impl<'a> Add<&Term<'a>> for f64 {
-    type Output = Expression<'a>;
-
-    fn add(self, rhs: &Term<'a>) -> Self::Output {
-        rhs + self
-    }
-}
\ No newline at end of file
@@ -1,15 +1,14 @@
-
-use super::Variable;
+use super::{Coefficient, Variable};
 
 // Represents a term in the linear program
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Term<'a> {
-    pub coefficient: f64,
+pub struct Term<'a, T = f64> {
+    pub coefficient: T,
     pub variable: Variable<'a>,
 }
 
-impl<'a> Term<'a> {
-    pub fn new(coefficient: f64, variable: Variable<'a>) -> Self {
+impl<'a, T> Term<'a, T> {
+    pub fn new(coefficient: T, variable: Variable<'a>) -> Self {
         Self { variable, coefficient }
     }
 }
@@ -17,25 +16,26 @@ impl<'a> Term<'a> {
 mod add;
 mod mul;
 mod div {
+    use super::super::Coefficient;
     use super::Term;
     use std::ops::Div;
 
-    impl<'a> Div<f64> for Term<'a> {
-        type Output = Term<'a>;
+    impl<'a, T: Coefficient> Div<T> for Term<'a, T> {
+        type Output = Term<'a, T>;
 
-        fn div(mut self, rhs: f64) -> Self::Output {
+        fn div(mut self, rhs: T) -> Self::Output {
             self.coefficient /= rhs;
             self            
         }
         
     }
 
-    impl<'a> Div<f64> for &Term<'a> {
-        type Output = Term<'a>;
+    impl<'a, T: Coefficient> Div<T> for &Term<'a, T> {
+        type Output = Term<'a, T>;
 
-        fn div(self, rhs: f64) -> Self::Output {
+        fn div(self, rhs: T) -> Self::Output {
             Term {
-                coefficient: self.coefficient / rhs,
+                coefficient: self.coefficient.clone() / rhs,
                 variable: self.variable,
             }
         }
@@ -43,8 +43,8 @@ mod div {
     }
 }
 
-impl<'a> From<Variable<'a>> for Term<'a> {
+impl<'a, T: Coefficient> From<Variable<'a>> for Term<'a, T> {
     fn from(val: Variable<'a>) -> Self {
-        Self { variable: val, coefficient: 1.0 }
+        Self { variable: val, coefficient: T::one() }
     }
 }
@@ -1,11 +1,12 @@
+use super::super::Coefficient;
 use super::Term;
 
 use std::ops::Mul;
 
-impl<'a> Mul<f64> for Term<'a> {
-    type Output = Term<'a>;
+impl<'a, T: Coefficient> Mul<T> for Term<'a, T> {
+    type Output = Term<'a, T>;
 
-    fn mul(self, rhs: f64) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         Self {
             coefficient: self.coefficient * rhs,
             variable: self.variable,
@@ -13,29 +14,13 @@ impl<'a> Mul<f64> for Term<'a> {
     }
 }
 
-impl<'a> Mul<Term<'a>> for f64 {
-    type Output = Term<'a>;
+impl<'a, T: Coefficient> Mul<T> for &Term<'a, T> {
+    type Output = Term<'a, T>;
 
-    fn mul(self, rhs: Term<'a>) -> Self::Output {
-        rhs * self
-    }
-}
-
-impl<'a> Mul<f64> for &Term<'a> {
-    type Output = Term<'a>;
-
-    fn mul(self, rhs: f64) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         Term {
-            coefficient: self.coefficient * rhs,
+            coefficient: self.coefficient.clone() * rhs,
             variable: self.variable,
         }
     }
 }
-
-impl<'a> Mul<&Term<'a>> for f64 {
-    type Output = Term<'a>;
-
-    fn mul(self, rhs: &Term<'a>) -> Self::Output {
-        rhs * self
-    }
-}
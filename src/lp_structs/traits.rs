@@ -1,6 +1,8 @@
+use std::fmt::Display;
+
 use super::{Expression, Variable as V};
-pub trait Substitutable<'a> {
-    fn substitute(&mut self, var: &V<'a>, expr: &Expression<'a>);
+pub trait Substitutable<'a, T = f64> {
+    fn substitute(&mut self, var: &V<'a>, expr: &Expression<'a, T>);
 }
 
 pub trait VariableContainer<'a> {
@@ -9,8 +11,8 @@ pub trait VariableContainer<'a> {
 
     fn variables_iter(&self) -> Self::I;
 
-    fn next_temp_variable(&self) -> V<'a> {
-        let index = self.variables_iter()
+    fn next_temp_id(&self) -> usize {
+        self.variables_iter()
             .filter_map(|var| {
                 if let V::Temp { id } = var {
                     Some(id)
@@ -20,16 +22,31 @@ pub trait VariableContainer<'a> {
             })
             .max()
             .map(|id| id + 1)
-            .unwrap_or(0);
-        V::Temp { id: index }
+            .unwrap_or(0)
+    }
+
+    fn next_temp_variable(&self) -> V<'a> {
+        V::Temp { id: self.next_temp_id() }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EvaluationError {
     UnsolvedVariable,
     CircularDependency,
 }
 
+impl Display for EvaluationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvaluationError::UnsolvedVariable => write!(f, "variable has no value"),
+            EvaluationError::CircularDependency => write!(f, "variable depends on itself"),
+        }
+    }
+}
+
+impl std::error::Error for EvaluationError {}
+
 pub struct RecursionGuard<'a, 'b> {
 variable: V<'a>,
     prev_guard: Option<&'b RecursionGuard<'a, 'b>>
@@ -62,19 +79,19 @@ impl<'a, 'b> RecursionGuard<'a, 'b> {
     }
 }
 
-pub trait Evaluable<'a> {
-    fn evaluate<X>(&self, source: &X) -> Result<f64, EvaluationError>
+pub trait Evaluable<'a, T = f64> {
+    fn evaluate<X>(&self, source: &X) -> Result<T, EvaluationError>
     where
-        X: EvaluationContext<'a>;
+        X: EvaluationContext<'a, T>;
     
-    fn evaluate_with_recursion_guard<X>(&self, source: &X, guard: &RecursionGuard) -> Result<f64, EvaluationError>
+    fn evaluate_with_recursion_guard<X>(&self, source: &X, guard: &RecursionGuard) -> Result<T, EvaluationError>
     where
-        X: EvaluationContext<'a>;
+        X: EvaluationContext<'a, T>;
 }
 
 
-pub trait EvaluationContext<'a> {
-    fn evaluate(&self, var: &V<'a>) -> Result<f64, EvaluationError>;
+pub trait EvaluationContext<'a, T = f64> {
+    fn evaluate(&self, var: &V<'a>) -> Result<T, EvaluationError>;
 
-    fn evaluate_with_recursion_guard(&self, var: &V<'a>, guard: &RecursionGuard) -> Result<f64, EvaluationError>;
+    fn evaluate_with_recursion_guard(&self, var: &V<'a>, guard: &RecursionGuard) -> Result<T, EvaluationError>;
 }
\ No newline at end of file
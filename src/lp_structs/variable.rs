@@ -1,8 +1,8 @@
 use std::{fmt::Display, ops::Mul};
-use super::Term;
+use super::{Coefficient, Term};
 
 // Represents a variable in the linear program with a unique index
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Variable<'a> {
     Named{ name: &'a str},
     Temp{ id: usize },
@@ -23,34 +23,18 @@ impl Display for Variable<'_> {
     }
 }
 
-impl<'a> Mul<f64> for Variable<'a> {
-    type Output = Term<'a>;
+impl<'a, T: Coefficient> Mul<T> for Variable<'a> {
+    type Output = Term<'a, T>;
 
-    fn mul(self, rhs: f64) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         Term::new(rhs, self)
     }
 }
 
-impl<'a> Mul<Variable<'a>> for f64 {
-    type Output = Term<'a>;
+impl<'a, T: Coefficient> Mul<T> for &Variable<'a> {
+    type Output = Term<'a, T>;
 
-    fn mul(self, rhs: Variable<'a>) -> Self::Output {
-        Term::new( self, rhs)
-    }
-}
-
-impl<'a> Mul<f64> for &Variable<'a> {
-    type Output = Term<'a>;
-
-    fn mul(self, rhs: f64) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         Term::new( rhs, *self)
     }
 }
-
-impl<'a> Mul<&Variable<'a>> for f64 {
-    type Output = Term<'a>;
-
-    fn mul(self, rhs: &Variable<'a>) -> Self::Output {
-        Term::new(self, *rhs)
-    }
-}
\ No newline at end of file
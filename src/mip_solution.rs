@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::time::Duration;
+
+use crate::lp_structs::RecursionGuard;
+use crate::{EvaluationContext, EvaluationError, SolveStatus, Variable};
+
+// Represents the result of solving a mixed-integer program by branch and bound
+#[derive(Clone, Debug)]
+pub struct MipSolution<'a> {
+    // Optimal also when the search stopped within the gaps allowed, and
+    // unbounded with the relaxation, which may have no integer solution
+    pub status: SolveStatus,
+    // The best integer solution found, NaN while there is none
+    pub objective_value: f64,
+    // Empty while no integer solution was found
+    pub values: HashMap<Variable<'a>, f64>,
+    // The best objective value any integer solution could still have
+    pub best_bound: f64,
+    // The distance to the best bound as a fraction of the objective value, or
+    // of one when that is smaller in size, infinite while there is no solution
+    pub gap: f64,
+    pub nodes: usize,
+    // Every pivot made, including those solving the root again after cuts
+    pub iterations: usize,
+    // Only filled in when the trace is requested in the solver options
+    pub progress: Vec<MipProgress>,
+}
+
+// Represents where branch and bound stood at some point of the search: the
+// nodes explored and left to explore, the objective value of the best
+// integer solution found, which is NaN while there is none, the best bound
+// and the gap between the two, and the time the search had taken
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MipProgress {
+    pub nodes: usize,
+    pub open_nodes: usize,
+    pub objective_value: f64,
+    pub best_bound: f64,
+    pub gap: f64,
+    pub elapsed: Duration,
+}
+
+impl Display for MipProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} nodes, {} open: incumbent {}, bound {}, gap {:.2}%",
+            self.nodes,
+            self.open_nodes,
+            self.objective_value,
+            self.best_bound,
+            100.0 * self.gap
+        )
+    }
+}
+
+impl<'a> MipSolution<'a> {
+    pub fn value(&self, variable: &Variable<'a>) -> Option<f64> {
+        self.values.get(variable).copied()
+    }
+}
+
+impl<'a> EvaluationContext<'a> for MipSolution<'a> {
+    fn evaluate(&self, var: &Variable<'a>) -> Result<f64, EvaluationError> {
+        self.value(var).ok_or(EvaluationError::UnsolvedVariable)
+    }
+
+    fn evaluate_with_recursion_guard(
+        &self,
+        var: &Variable<'a>,
+        _guard: &RecursionGuard,
+    ) -> Result<f64, EvaluationError> {
+        self.evaluate(var)
+    }
+}
+
+impl Display for MipSolution<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}: {} (bound {})", self.status, self.objective_value, self.best_bound)?;
+        let mut values = self.values.iter().collect::<Vec<_>>();
+        values.sort_by_key(|(variable, _)| variable.to_string());
+        for (variable, value) in values {
+            writeln!(f, "{} = {}", variable, value)?;
+        }
+        Ok(())
+    }
+}
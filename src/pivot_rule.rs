@@ -0,0 +1,28 @@
+use std::fmt::Display;
+
+// Represents how the simplex method chooses the variable to enter the basis
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PivotRule {
+    // The variable with the largest reduced cost
+    Dantzig,
+    // The lowest indexed variable that improves the objective, with ties in
+    // the ratio test broken the same way. Never cycles on degenerate programs
+    Bland,
+    // The variable whose edge improves the objective the most per unit of
+    // distance travelled
+    SteepestEdge,
+    // An approximation of steepest edge that keeps cheap estimates of the
+    // edge lengths relative to a reference set of variables
+    Devex,
+}
+
+impl Display for PivotRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PivotRule::Dantzig => write!(f, "Dantzig"),
+            PivotRule::Bland => write!(f, "Bland"),
+            PivotRule::SteepestEdge => write!(f, "steepest edge"),
+            PivotRule::Devex => write!(f, "devex"),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::Variable;
+
+// Represents the interval a number can move within without the optimal basis
+// changing, which is infinite at either end where nothing limits it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SensitivityRange {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl SensitivityRange {
+    pub fn contains(&self, value: f64) -> bool {
+        self.lower <= value && value <= self.upper
+    }
+}
+
+impl Display for SensitivityRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}]", self.lower, self.upper)
+    }
+}
+
+// Represents how far each objective coefficient and right hand side can move
+// while the final basis stays optimal
+#[derive(Clone, Debug, PartialEq)]
+pub struct SensitivityReport<'a> {
+    // Per named variable, moving one coefficient at a time. The values of the
+    // variables stay the same across the range
+    pub objective_coefficients: HashMap<Variable<'a>, SensitivityRange>,
+    // One per constraint in program order. The values change linearly across
+    // the range and the objective moves at the constraint's shadow price
+    pub right_hand_sides: Vec<SensitivityRange>,
+}
+
+impl Display for SensitivityReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut coefficients = self.objective_coefficients.iter().collect::<Vec<_>>();
+        coefficients.sort_by_key(|(variable, _)| variable.to_string());
+        for (variable, range) in coefficients {
+            writeln!(f, "cost of {}: {}", variable, range)?;
+        }
+        for (index, range) in self.right_hand_sides.iter().enumerate() {
+            writeln!(f, "right hand side of constraint {}: {}", index, range)?;
+        }
+        Ok(())
+    }
+}
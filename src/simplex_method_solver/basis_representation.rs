@@ -0,0 +1,44 @@
+use super::sparse_matrix::SparseMatrix;
+
+// Raised when a change of basis leaves the basis matrix singular
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SingularBasis;
+
+// The linear algebra the simplex method needs from its basis B. Vectors
+// indexed by row hold one entry per constraint, vectors indexed by position
+// hold one entry per basic variable in the order of the basis, and columns
+// are the columns of the constraint matrix A the representation was made from
+pub trait BasisRepresentation {
+    // Starts from the given matrix and a nonsingular set of basic columns,
+    // which may end up at different positions from the order given
+    fn new(matrix: SparseMatrix, basis: Vec<usize>) -> Self;
+
+    // The basic column at every position
+    fn basis(&self) -> &[usize];
+
+    // Replaces the costs the reduced costs are priced against
+    fn set_costs(&mut self, costs: &[f64]);
+
+    // c - A^T B^-T c_B, the rate at which each column changes the objective
+    fn reduced_costs(&mut self) -> Vec<f64>;
+
+    // B^-T c_B by row, the rate at which the right hand side of each row
+    // changes the objective
+    fn duals(&mut self) -> Vec<f64>;
+
+    // B^-1 a_j, the column expressed in terms of the basis, by position
+    fn column(&mut self, column: usize) -> Vec<f64>;
+
+    // e_p^T B^-1 A, the row of the tableau at the given position
+    fn row(&mut self, position: usize) -> Vec<f64>;
+
+    // B^-1 v for a vector indexed by row
+    fn ftran(&mut self, vector: &[f64]) -> Vec<f64>;
+
+    // (B^-1 a_j)^T v for every column, given a vector indexed by position
+    fn column_products(&mut self, vector: &[f64]) -> Vec<f64>;
+
+    // Brings the column into the basis at the given position, where alpha is
+    // the column expressed in terms of the current basis
+    fn pivot(&mut self, position: usize, column: usize, alpha: &[f64]) -> Result<(), SingularBasis>;
+}
@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::{
+    Basis, Constraint, Expression, LinearProgram, MipProgress, MipSolution, MixedIntegerProgram, NodeSelection,
+    Operator, Solution, SolveError, SolveStatus, SolverOptions, Variable,
+};
+
+use super::{gomory_cuts::gomory_cuts, heuristics::Heuristics, solve};
+
+// A subproblem of the search, the program with the bounds branched on to
+// reach it and the cuts found above it added as constraints, along with the
+// objective value of its parent's relaxation, which bounds its own, and the
+// basis its parent's relaxation finished with to start its own from
+struct Node<'a> {
+    branches: Vec<Constraint<'a>>,
+    bound: f64,
+    depth: usize,
+    basis: Option<Basis<'a>>,
+}
+
+// Solves a mixed-integer program by solving the relaxation that drops the
+// integrality of its variables, then splitting the program in two on an
+// integer variable whose value is fractional, one side holding it at or
+// below the integer beneath its value and the other at or above the integer
+// above it. A node is dropped once its relaxation cannot beat the best
+// integer solution found so far. Rounds of Gomory cuts tighten the
+// relaxation at the root before it is branched on, and are kept by every
+// node below it. Heuristics look for integer solutions near the optimum of
+// every relaxation that has to be branched on, so that nodes can be pruned
+// early. Without branching the search stops at the root once the cuts run
+// out, which is the cutting plane method. The search stops early once the
+// best integer solution is within the gaps allowed of the best bound, or
+// once it reaches the node or time limit, and the relaxations are only given
+// the time left. Objective values are kept as if the objective were
+// maximised, whichever way it goes
+pub struct BranchAndBound<'a, 'b> {
+    mip: &'b MixedIntegerProgram<'a>,
+    options: SolverOptions,
+    branching: bool,
+    sense: f64,
+    nodes: Vec<Node<'a>>,
+    incumbent: Option<(f64, HashMap<Variable<'a>, f64>)>,
+    explored: usize,
+    iterations: usize,
+    start: Instant,
+    deadline: Option<Instant>,
+    record_progress: bool,
+    progress: Vec<MipProgress>,
+}
+
+impl<'a, 'b> BranchAndBound<'a, 'b> {
+    pub fn new(mip: &'b MixedIntegerProgram<'a>, options: &SolverOptions, branching: bool) -> BranchAndBound<'a, 'b> {
+        BranchAndBound {
+            mip,
+            options: SolverOptions { record_trace: false, ..options.clone() },
+            branching,
+            sense: if mip.program.objective.maximize { 1.0 } else { -1.0 },
+            nodes: vec![Node { branches: vec![], bound: f64::INFINITY, depth: 0, basis: None }],
+            incumbent: None,
+            explored: 0,
+            iterations: 0,
+            start: Instant::now(),
+            deadline: options.time_limit.map(|limit| Instant::now() + limit),
+            record_progress: options.record_trace,
+            progress: vec![],
+        }
+    }
+
+    pub fn solve(mut self) -> Result<MipSolution<'a>, SolveError> {
+        loop {
+            self.record_progress();
+            if self.is_within_gap() {
+                return Ok(self.finish(SolveStatus::Optimal));
+            }
+            if self.nodes.is_empty() {
+                break;
+            }
+            if self.options.node_limit.is_some_and(|limit| self.explored >= limit) {
+                return Ok(self.finish(SolveStatus::NodeLimit));
+            }
+            if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Ok(self.finish(SolveStatus::TimeLimit));
+            }
+            let Some(mut node) = self.select() else {
+                break;
+            };
+            if self.is_pruned(node.bound) {
+                continue;
+            }
+            let solution = self.solve_node(&mut node)?;
+            match solution.status {
+                SolveStatus::Optimal => {}
+                SolveStatus::Infeasible => continue,
+                // the node is left to explore, so the bound still counts it
+                status => {
+                    let bound = if status == SolveStatus::Unbounded { f64::INFINITY } else { node.bound };
+                    self.nodes.push(Node { bound, ..node });
+                    return Ok(self.finish(status));
+                }
+            }
+
+            let bound = self.sense * solution.objective_value;
+            if self.is_pruned(bound) {
+                continue;
+            }
+            let Some((variable, value)) = self.most_fractional(&solution.values) else {
+                self.incumbent = Some((bound, solution.values));
+                continue;
+            };
+            // the cuts ran out before the relaxation became integer
+            if !self.branching {
+                self.nodes.push(Node { bound, ..node });
+                return Ok(self.finish(SolveStatus::IterationLimit));
+            }
+            self.run_heuristics(&node, &solution)?;
+            if self.is_pruned(bound) {
+                continue;
+            }
+            self.branch(node, variable, value, bound, solution.basis);
+        }
+        let status = if self.incumbent.is_some() { SolveStatus::Optimal } else { SolveStatus::Infeasible };
+        Ok(self.finish(status))
+    }
+
+    // Solves the relaxation of the node. At the root the cuts found from its
+    // optimal tableau are added to it, and it is solved again from the basis
+    // it finished with, until the relaxation is integer, no more cuts are
+    // found or the rounds of cuts allowed are used up
+    fn solve_node(&mut self, node: &mut Node<'a>) -> Result<Solution<'a>, SolveError> {
+        let mut solution = solve(&self.program_at(node), &self.relaxation_options(), node.basis.as_ref())?;
+        self.explored += 1;
+        self.iterations += solution.iterations;
+        let mut rounds = 0;
+        while node.depth == 0
+            && rounds < self.options.cut_rounds
+            && solution.status == SolveStatus::Optimal
+            && self.most_fractional(&solution.values).is_some()
+        {
+            let program = self.program_at(node);
+            let cuts = gomory_cuts(&program, &self.mip.integer_variables, &solution, &self.options);
+            if cuts.is_empty() {
+                break;
+            }
+            node.branches.extend(cuts);
+            solution = solve(&self.program_at(node), &self.relaxation_options(), solution.basis.as_ref())?;
+            self.iterations += solution.iterations;
+            rounds += 1;
+        }
+        Ok(solution)
+    }
+
+    // Looks for integer solutions near the fractional optimum of the node's
+    // relaxation, rounding it at every node and at the root also pumping it
+    // towards an integer solution, when none has been found yet, and diving
+    // from it. Whatever the heuristics find becomes the best integer solution
+    // when it is better than the one there is
+    fn run_heuristics(&mut self, node: &Node<'a>, solution: &Solution<'a>) -> Result<(), SolveError> {
+        let mip = self.mip;
+        let options = self.relaxation_options();
+        let mut found = vec![];
+        if options.rounding {
+            let heuristics = Heuristics::new(&mip.program, &mip.integer_variables, &options);
+            found.extend(heuristics.simple_rounding(&solution.values));
+        }
+        if node.depth == 0 {
+            let program = self.program_at(node);
+            let mut heuristics = Heuristics::new(&program, &mip.integer_variables, &options);
+            if options.feasibility_pump && self.incumbent.is_none() && found.is_empty() {
+                found.extend(heuristics.feasibility_pump(solution)?);
+            }
+            if options.diving {
+                found.extend(heuristics.fractional_diving(solution)?);
+            }
+            self.iterations += heuristics.iterations;
+        }
+        for (objective_value, values) in found {
+            let value = self.sense * objective_value;
+            if self.incumbent.as_ref().map_or(true, |(incumbent, _)| value > *incumbent) {
+                self.incumbent = Some((value, values));
+            }
+        }
+        Ok(())
+    }
+
+    // Takes the next node to explore off the list
+    fn select(&mut self) -> Option<Node<'a>> {
+        match self.options.node_selection {
+            NodeSelection::DepthFirst => self.nodes.pop(),
+            NodeSelection::BestBound => {
+                // ties go to the deeper node, which is closer to being integer
+                let index = (0..self.nodes.len()).max_by(|&i, &j| {
+                    let (a, b) = (&self.nodes[i], &self.nodes[j]);
+                    a.bound.total_cmp(&b.bound).then(a.depth.cmp(&b.depth))
+                })?;
+                Some(self.nodes.swap_remove(index))
+            }
+        }
+    }
+
+    // Whether a relaxation with this objective value cannot beat the best
+    // integer solution found
+    fn is_pruned(&self, bound: f64) -> bool {
+        self.incumbent.as_ref().is_some_and(|(value, _)| {
+            bound <= value + self.options.primal_feasibility_tolerance * value.abs().max(1.0)
+        })
+    }
+
+    fn program_at(&self, node: &Node<'a>) -> LinearProgram<'a> {
+        LinearProgram {
+            objective: self.mip.program.objective.clone(),
+            constraints: self.mip.program.constraints.iter().chain(&node.branches).cloned().collect(),
+        }
+    }
+
+    // The integer variable whose value is furthest from an integer, if any
+    // of them is further than the integrality tolerance
+    fn most_fractional(&self, values: &HashMap<Variable<'a>, f64>) -> Option<(Variable<'a>, f64)> {
+        self.mip
+            .integer_variables
+            .iter()
+            .filter_map(|variable| values.get(variable).map(|&value| (*variable, value)))
+            .map(|(variable, value)| (variable, value, (value - value.round()).abs()))
+            .filter(|&(_, _, distance)| distance > self.options.integrality_tolerance)
+            .max_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(variable, value, _)| (variable, value))
+    }
+
+    // Splits the node in two on the variable. The child whose side the
+    // value is nearer is put on the list last, so it is explored first when
+    // going depth first
+    fn branch(&mut self, node: Node<'a>, variable: Variable<'a>, value: f64, bound: f64, basis: Option<Basis<'a>>) {
+        let child = |operator, rhs: f64| {
+            let mut branches = node.branches.clone();
+            branches.push(Constraint { lhs: variable.into(), operator, rhs: Expression::zero() + rhs });
+            Node { branches, bound, depth: node.depth + 1, basis: basis.clone() }
+        };
+        let down = child(Operator::LTE, value.floor());
+        let up = child(Operator::GTE, value.ceil());
+        if value - value.floor() < 0.5 {
+            self.nodes.extend([up, down]);
+        } else {
+            self.nodes.extend([down, up]);
+        }
+    }
+
+    // The options the relaxations are solved with, which only have the time
+    // left before the search has to stop
+    fn relaxation_options(&self) -> SolverOptions {
+        SolverOptions {
+            time_limit: self.deadline.map(|deadline| deadline.saturating_duration_since(Instant::now())),
+            ..self.options.clone()
+        }
+    }
+
+    // The best bound over the nodes left to explore and the best integer
+    // solution found
+    fn best_bound(&self) -> f64 {
+        self.nodes
+            .iter()
+            .map(|node| node.bound)
+            .chain(self.incumbent.as_ref().map(|(value, _)| *value))
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    // How far the best bound is from the best integer solution, relative to
+    // its objective value but at least one so a zero incumbent has a finite
+    // gap, which is infinite while there is none
+    fn gap(&self) -> f64 {
+        match &self.incumbent {
+            Some((value, _)) => (self.best_bound() - value).max(0.0) / value.abs().max(1.0),
+            None => f64::INFINITY,
+        }
+    }
+
+    fn is_within_gap(&self) -> bool {
+        self.incumbent.as_ref().is_some_and(|(value, _)| {
+            self.best_bound() - value <= self.options.absolute_gap || self.gap() <= self.options.relative_gap
+        })
+    }
+
+    // Records where the search stands whenever the best integer solution or
+    // the best bound has moved since it was last recorded
+    fn record_progress(&mut self) {
+        if !self.record_progress {
+            return;
+        }
+        let progress = MipProgress {
+            nodes: self.explored,
+            open_nodes: self.nodes.len(),
+            objective_value: self.incumbent.as_ref().map_or(f64::NAN, |(value, _)| self.sense * value),
+            best_bound: self.sense * self.best_bound(),
+            gap: self.gap(),
+            elapsed: self.start.elapsed(),
+        };
+        let moved = self.progress.last().map_or(true, |last| {
+            last.objective_value.total_cmp(&progress.objective_value).is_ne()
+                || last.best_bound.total_cmp(&progress.best_bound).is_ne()
+        });
+        if moved {
+            self.progress.push(progress);
+        }
+    }
+
+    fn finish(mut self, status: SolveStatus) -> MipSolution<'a> {
+        self.record_progress();
+        let best_bound = self.sense * self.best_bound();
+        let gap = self.gap();
+        let (objective_value, values) = match self.incumbent {
+            Some((value, values)) => (self.sense * value, values),
+            None if status == SolveStatus::Unbounded => (self.sense * f64::INFINITY, HashMap::new()),
+            None => (f64::NAN, HashMap::new()),
+        };
+        MipSolution {
+            status,
+            objective_value,
+            values,
+            best_bound,
+            gap,
+            nodes: self.explored,
+            iterations: self.iterations,
+            progress: self.progress,
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::{BTreeSet, HashSet};
+
+// Pivots this much smaller than the diagonal entry of their row in the
+// matrix are taken to come from rows that depend on the rows before them
+const DEPENDENCY_TOLERANCE: f64 = 1e-30;
+
+// Once every row left is joined to at least this fraction of the others, the
+// rest of the matrix is taken to be dense and eliminated in one block, as
+// keeping track of its fill in costs far more than the few zeros it saves
+const DENSE_FRACTION: f64 = 0.5;
+
+// A factorization M = L D L^T of a symmetric positive semidefinite matrix,
+// with L unit lower triangular and D diagonal, for matrices that share one
+// pattern of nonzero entries and differ only in their values.
+//
+// Step s of the elimination eliminates row order[s], and steps[row] gives the
+// step a row is eliminated at. lower[s] holds the entries below the diagonal
+// of column s of L as (step, value) pairs in order of step, and diagonal[s]
+// the entry of D. row_entries[s] lists the earlier steps with an entry in row
+// s of L, along with the index of that entry in their column
+pub struct CholeskyFactorization {
+    order: Vec<usize>,
+    steps: Vec<usize>,
+    lower: Vec<Vec<(usize, f64)>>,
+    row_entries: Vec<Vec<(usize, usize)>>,
+    diagonal: Vec<f64>,
+}
+
+impl CholeskyFactorization {
+    // Orders the rows by minimum degree, eliminating at every step the row
+    // joined to the fewest others that are left, which keeps the fill in of
+    // L low. Eliminating a row joins all of its neighbours to each other, and
+    // the neighbours it has when it is eliminated are where its column of L
+    // has entries. The pattern gives the columns of the nonzero entries of
+    // every row of the matrix, which has to be symmetric
+    pub fn new(pattern: &[Vec<usize>]) -> CholeskyFactorization {
+        let size = pattern.len();
+        let mut adjacency = pattern
+            .iter()
+            .enumerate()
+            .map(|(row, columns)| columns.iter().copied().filter(|&column| column != row).collect())
+            .collect::<Vec<HashSet<usize>>>();
+        let mut queue = adjacency
+            .iter()
+            .enumerate()
+            .map(|(row, neighbours)| (neighbours.len(), row))
+            .collect::<BTreeSet<_>>();
+
+        let mut order = Vec::with_capacity(size);
+        let mut steps = vec![0; size];
+        let mut structure = Vec::with_capacity(size);
+        while let Some((degree, row)) = queue.pop_first() {
+            let remaining = queue.len();
+            if remaining > 0 && degree as f64 >= DENSE_FRACTION * remaining as f64 {
+                queue.insert((degree, row));
+                let rest = queue.iter().map(|&(_, row)| row).collect::<Vec<_>>();
+                for (index, &row) in rest.iter().enumerate() {
+                    steps[row] = order.len();
+                    order.push(row);
+                    structure.push(rest[index + 1..].to_vec());
+                }
+                break;
+            }
+            steps[row] = order.len();
+            order.push(row);
+            let neighbours = adjacency[row].drain().collect::<Vec<_>>();
+            for &neighbour in &neighbours {
+                queue.remove(&(adjacency[neighbour].len(), neighbour));
+                adjacency[neighbour].remove(&row);
+                for &other in &neighbours {
+                    if other != neighbour {
+                        adjacency[neighbour].insert(other);
+                    }
+                }
+                queue.insert((adjacency[neighbour].len(), neighbour));
+            }
+            structure.push(neighbours);
+        }
+
+        let lower = structure
+            .into_iter()
+            .map(|neighbours| {
+                let mut column = neighbours.into_iter().map(|row| (steps[row], 0.0)).collect::<Vec<_>>();
+                column.sort_by_key(|&(step, _)| step);
+                column
+            })
+            .collect::<Vec<Vec<(usize, f64)>>>();
+        let mut row_entries = vec![vec![]; size];
+        for (step, column) in lower.iter().enumerate() {
+            for (index, &(row, _)) in column.iter().enumerate() {
+                row_entries[row].push((step, index));
+            }
+        }
+        CholeskyFactorization {
+            order,
+            steps,
+            lower,
+            row_entries,
+            diagonal: vec![0.0; size],
+        }
+    }
+
+    // Factorizes a matrix with the pattern the factorization was made for,
+    // given as the nonzero entries of every row, one column of L at a time
+    // from the columns before it. A row that depends on those before it
+    // leaves a pivot of about zero, which is replaced by infinity so that
+    // solving gives zero for it rather than dividing by round-off
+    pub fn factorize(&mut self, matrix: &[Vec<(usize, f64)>]) {
+        let mut work = vec![0.0; self.order.len()];
+        for step in 0..self.order.len() {
+            for &(column, value) in &matrix[self.order[step]] {
+                let other = self.steps[column];
+                if other >= step {
+                    work[other] += value;
+                }
+            }
+            let original = work[step];
+            for &(earlier, index) in &self.row_entries[step] {
+                // the column of a dependent row is zero, and its pivot infinite
+                let entry = self.lower[earlier][index].1;
+                if entry == 0.0 {
+                    continue;
+                }
+                let factor = entry * self.diagonal[earlier];
+                work[step] -= entry * factor;
+                for &(row, value) in &self.lower[earlier][index + 1..] {
+                    work[row] -= value * factor;
+                }
+            }
+
+            let pivot = work[step];
+            work[step] = 0.0;
+            self.diagonal[step] = if pivot > DEPENDENCY_TOLERANCE * original {
+                pivot
+            } else {
+                f64::INFINITY
+            };
+            for entry in self.lower[step].iter_mut() {
+                entry.1 = work[entry.0] / self.diagonal[step];
+                work[entry.0] = 0.0;
+            }
+        }
+    }
+
+    // Solves M x = rhs by solving L z = rhs, then D w = z, then L^T x = w
+    pub fn solve(&self, rhs: &[f64]) -> Vec<f64> {
+        let mut work = self.order.iter().map(|&row| rhs[row]).collect::<Vec<_>>();
+        for step in 0..work.len() {
+            let value = work[step];
+            if value != 0.0 {
+                for &(row, entry) in &self.lower[step] {
+                    work[row] -= entry * value;
+                }
+            }
+        }
+        for (value, diagonal) in work.iter_mut().zip(&self.diagonal) {
+            *value /= diagonal;
+        }
+        for step in (0..work.len()).rev() {
+            let sum = self.lower[step].iter().map(|&(row, entry)| entry * work[row]).sum::<f64>();
+            work[step] -= sum;
+        }
+
+        let mut solution = vec![0.0; work.len()];
+        for (step, &row) in self.order.iter().enumerate() {
+            solution[row] = work[step];
+        }
+        solution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solves_with_fill_in_and_dependent_rows() {
+        // an arrow matrix, which fills in completely when the dense row is
+        // eliminated first, followed by a copy of its last row
+        let matrix = vec![
+            vec![(0, 4.0), (1, 1.0), (2, 1.0), (3, 1.0), (4, 1.0)],
+            vec![(0, 1.0), (1, 3.0)],
+            vec![(0, 1.0), (2, 2.0)],
+            vec![(0, 1.0), (3, 5.0), (4, 5.0)],
+            vec![(0, 1.0), (3, 5.0), (4, 5.0)],
+        ];
+        let pattern = matrix
+            .iter()
+            .map(|entries| entries.iter().map(|&(column, _)| column).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut factorization = CholeskyFactorization::new(&pattern);
+        factorization.factorize(&matrix);
+
+        let expected = [1.0, 2.0, -1.0, 0.5, 0.0];
+        let rhs = matrix
+            .iter()
+            .map(|entries| entries.iter().map(|&(column, value)| value * expected[column]).sum::<f64>())
+            .collect::<Vec<_>>();
+        let solution = factorization.solve(&rhs);
+        // the last two rows are the same, so only their sum is determined
+        for row in 0..3 {
+            assert!((solution[row] - expected[row]).abs() < 1e-9);
+        }
+        assert!((solution[3] + solution[4] - 0.5).abs() < 1e-9);
+    }
+}
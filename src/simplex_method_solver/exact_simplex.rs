@@ -0,0 +1,491 @@
+use std::collections::HashMap;
+
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+
+use crate::{
+    ExactSolution, Expression, LinearProgram, Operator, RationalCoefficient, SolveStatus, Variable,
+    VariableContainer,
+};
+
+// The number of pivots after which the method gives up, as many as the
+// floating point solver allows by default
+const ITERATION_LIMIT: usize = 50_000;
+
+// The coefficient of every variable in an expression, read as fractions
+fn coefficients<'a, T: RationalCoefficient>(expression: &Expression<'a, T>) -> HashMap<Variable<'a>, BigRational> {
+    let mut coefficients = HashMap::new();
+    for term in &expression.terms {
+        *coefficients.entry(term.variable).or_insert_with(BigRational::zero) += term.coefficient.to_rational();
+    }
+    coefficients
+}
+
+// The simplex method over exact fractions on the full tableau, for small
+// programs whose answers have to be exact. Every constraint is kept as a
+// row, turned around where needed so its right hand side is nonnegative, and
+// every named variable that no constraint holds at or above zero is split into
+// the difference of two nonnegative columns. The rows start with their slack
+// or an artificial variable as their basic column, which is the unit column
+// the duals are read from, and Bland's rule keeps the method from cycling as
+// there is no round-off to break ties on degenerate vertices
+pub struct ExactSimplex<'a> {
+    variables: Vec<Variable<'a>>,
+    // the column of every named variable and the column subtracted from it
+    // when it was split
+    columns: Vec<(usize, Option<usize>)>,
+    // the constraint that keeps every unsplit variable at or above zero,
+    // along with its coefficient there
+    sign_rows: Vec<Option<(usize, BigRational)>>,
+    // the costs of the columns, to be maximised
+    costs: Vec<BigRational>,
+    objective_coefficients: HashMap<Variable<'a>, BigRational>,
+    constant: BigRational,
+    maximize: bool,
+    // every row holds its entries in every column followed by its right hand
+    // side, and the objective row holds the reduced costs followed by the
+    // value of the objective being maximised
+    rows: Vec<Vec<BigRational>>,
+    objective: Vec<BigRational>,
+    basis: Vec<usize>,
+    unit_columns: Vec<usize>,
+    flipped: Vec<bool>,
+    artificial_start: usize,
+    iterations: usize,
+}
+
+impl<'a> ExactSimplex<'a> {
+    pub fn new<T: RationalCoefficient>(program: &LinearProgram<'a, T>) -> ExactSimplex<'a> {
+        let variables = program.variables_iter().collect::<Vec<_>>();
+        let constraints = program
+            .constraints
+            .iter()
+            .map(|c| {
+                let mut entries = coefficients(&c.lhs);
+                for (variable, coefficient) in coefficients(&c.rhs) {
+                    *entries.entry(variable).or_insert_with(BigRational::zero) -= coefficient;
+                }
+                entries.retain(|_, coefficient| !coefficient.is_zero());
+                let rhs = c.rhs.constant.to_rational() - c.lhs.constant.to_rational();
+                (entries, c.operator, rhs)
+            })
+            .collect::<Vec<_>>();
+
+        // a constraint on a single variable that keeps it at or above zero
+        // saves splitting it
+        let mut nonnegative = vec![false; variables.len()];
+        let mut sign_rows = vec![None; variables.len()];
+        for (row, (entries, operator, rhs)) in constraints.iter().enumerate() {
+            if entries.len() != 1 {
+                continue;
+            }
+            let (variable, coefficient) = entries.iter().next().unwrap();
+            let bounds_below = match operator {
+                Operator::GTE => coefficient.is_positive(),
+                Operator::LTE => coefficient.is_negative(),
+                Operator::Eq => true,
+            };
+            if bounds_below && !(rhs / coefficient).is_negative() {
+                let index = variables.iter().position(|var| var == variable).unwrap();
+                nonnegative[index] = true;
+                sign_rows[index].get_or_insert((row, coefficient.clone()));
+            }
+        }
+        let mut count = 0;
+        let mut next_column = || {
+            count += 1;
+            count - 1
+        };
+        let columns = nonnegative
+            .iter()
+            .map(|&nonnegative| (next_column(), (!nonnegative).then(&mut next_column)))
+            .collect::<Vec<_>>();
+        let structural = count;
+
+        let maximize = program.objective.maximize;
+        let objective_coefficients = coefficients(&program.objective.expression);
+        let mut costs = vec![BigRational::zero(); structural];
+        for (variable, &(column, negative)) in variables.iter().zip(&columns) {
+            let cost = objective_coefficients.get(variable).cloned().unwrap_or_else(BigRational::zero);
+            let cost = if maximize { cost } else { -cost };
+            if let Some(negative) = negative {
+                costs[negative] = -cost.clone();
+            }
+            costs[column] = cost;
+        }
+
+        // the slacks and surpluses come after the named variables, followed
+        // by the artificial variables
+        let flipped = constraints.iter().map(|(_, _, rhs)| rhs.is_negative()).collect::<Vec<_>>();
+        let operators = constraints
+            .iter()
+            .zip(&flipped)
+            .map(|((_, operator, _), &flipped)| if flipped { operator.flip() } else { *operator })
+            .collect::<Vec<_>>();
+        let mut slack_columns = vec![];
+        let mut width = structural;
+        for operator in &operators {
+            slack_columns.push((*operator != Operator::Eq).then_some(width));
+            if *operator != Operator::Eq {
+                width += 1;
+            }
+        }
+        let artificial_start = width;
+        let mut unit_columns = vec![];
+        for (row, operator) in operators.iter().enumerate() {
+            match operator {
+                Operator::LTE => unit_columns.push(slack_columns[row].unwrap()),
+                _ => {
+                    unit_columns.push(width);
+                    width += 1;
+                }
+            }
+        }
+        costs.resize(width, BigRational::zero());
+
+        let mut rows = vec![vec![BigRational::zero(); width + 1]; constraints.len()];
+        for (row, (entries, _, rhs)) in constraints.into_iter().enumerate() {
+            let sign = if flipped[row] { -BigRational::one() } else { BigRational::one() };
+            for (variable, coefficient) in entries {
+                let index = variables.iter().position(|var| *var == variable).unwrap();
+                let (column, negative) = columns[index];
+                if let Some(negative) = negative {
+                    rows[row][negative] = -&sign * &coefficient;
+                }
+                rows[row][column] = &sign * coefficient;
+            }
+            if let Some(slack) = slack_columns[row] {
+                rows[row][slack] = match operators[row] {
+                    Operator::GTE => -BigRational::one(),
+                    _ => BigRational::one(),
+                };
+            }
+            rows[row][unit_columns[row]] = BigRational::one();
+            rows[row][width] = sign * rhs;
+        }
+
+        ExactSimplex {
+            variables,
+            columns,
+            sign_rows,
+            costs,
+            objective_coefficients,
+            constant: program.objective.expression.constant.to_rational(),
+            maximize,
+            rows,
+            objective: vec![],
+            basis: unit_columns.clone(),
+            unit_columns,
+            flipped,
+            artificial_start,
+            iterations: 0,
+        }
+    }
+
+    // Replaces the objective being maximised, pricing out the basic columns
+    fn load_objective(&mut self, costs: &[BigRational]) {
+        let mut objective = costs.iter().map(|cost| -cost).collect::<Vec<_>>();
+        objective.push(BigRational::zero());
+        for (row, &basic) in self.rows.iter().zip(&self.basis) {
+            if costs[basic].is_zero() {
+                continue;
+            }
+            for (entry, value) in objective.iter_mut().zip(row) {
+                *entry += &costs[basic] * value;
+            }
+        }
+        self.objective = objective;
+    }
+
+    fn pivot(&mut self, position: usize, column: usize) {
+        let pivot = self.rows[position][column].clone();
+        for value in self.rows[position].iter_mut() {
+            *value /= &pivot;
+        }
+        let pivot_row = self.rows[position].clone();
+        for (index, row) in self.rows.iter_mut().enumerate() {
+            let factor = row[column].clone();
+            if index == position || factor.is_zero() {
+                continue;
+            }
+            for (value, entry) in row.iter_mut().zip(&pivot_row) {
+                *value -= &factor * entry;
+            }
+        }
+        let factor = self.objective[column].clone();
+        if !factor.is_zero() {
+            for (value, entry) in self.objective.iter_mut().zip(&pivot_row) {
+                *value -= &factor * entry;
+            }
+        }
+        self.basis[position] = column;
+        self.iterations += 1;
+    }
+
+    // Pivots the lowest indexed column that improves the objective into the
+    // basis in place of the lowest indexed of the basic columns that limit
+    // it, until no column improves it. Artificial variables never come back
+    fn iterate(&mut self) -> SolveStatus {
+        let rhs = self.objective.len() - 1;
+        while self.iterations < ITERATION_LIMIT {
+            let Some(column) = (0..self.artificial_start).find(|&column| self.objective[column].is_negative())
+            else {
+                return SolveStatus::Optimal;
+            };
+            let mut leaving: Option<(usize, BigRational)> = None;
+            for (position, row) in self.rows.iter().enumerate() {
+                if !row[column].is_positive() {
+                    continue;
+                }
+                let ratio = &row[rhs] / &row[column];
+                leaving = match leaving {
+                    Some((best, best_ratio))
+                        if best_ratio < ratio
+                            || best_ratio == ratio && self.basis[best] < self.basis[position] =>
+                    {
+                        Some((best, best_ratio))
+                    }
+                    _ => Some((position, ratio)),
+                };
+            }
+            let Some((position, _)) = leaving else {
+                return SolveStatus::Unbounded;
+            };
+            self.pivot(position, column);
+        }
+        SolveStatus::IterationLimit
+    }
+
+    // Phase I maximises minus the sum of the artificial variables, which
+    // reaches zero only if the program is feasible. The artificial variables
+    // left in the basis at zero are then pivoted out wherever their row has
+    // another column to pivot on, and the rows where none does are redundant
+    fn solve_phase_one(&mut self) -> SolveStatus {
+        let mut costs = vec![BigRational::zero(); self.costs.len()];
+        for cost in &mut costs[self.artificial_start..] {
+            *cost = -BigRational::one();
+        }
+        self.load_objective(&costs);
+        let status = self.iterate();
+        if status != SolveStatus::Optimal {
+            return status;
+        }
+        if self.objective.last().unwrap().is_negative() {
+            return SolveStatus::Infeasible;
+        }
+        for position in 0..self.rows.len() {
+            if self.basis[position] < self.artificial_start {
+                continue;
+            }
+            if let Some(column) = (0..self.artificial_start).find(|&column| !self.rows[position][column].is_zero()) {
+                self.pivot(position, column);
+            }
+        }
+        SolveStatus::Optimal
+    }
+
+    fn run(&mut self) -> SolveStatus {
+        if self.artificial_start < self.costs.len() {
+            let status = self.solve_phase_one();
+            if status != SolveStatus::Optimal {
+                return status;
+            }
+        }
+        let costs = self.costs.clone();
+        self.load_objective(&costs);
+        self.iterate()
+    }
+
+    pub fn solve(mut self) -> ExactSolution<'a> {
+        let status = self.run();
+        let rhs = self.objective.len() - 1;
+        let mut column_values = vec![BigRational::zero(); self.costs.len()];
+        for (row, &basic) in self.rows.iter().zip(&self.basis) {
+            column_values[basic] = row[rhs].clone();
+        }
+        let values = if status == SolveStatus::Infeasible {
+            HashMap::new()
+        } else {
+            self.variables
+                .iter()
+                .zip(&self.columns)
+                .filter(|(variable, _)| matches!(variable, Variable::Named { .. }))
+                .map(|(&variable, &(column, negative))| {
+                    let value = match negative {
+                        Some(negative) => &column_values[column] - &column_values[negative],
+                        None => column_values[column].clone(),
+                    };
+                    (variable, value)
+                })
+                .collect()
+        };
+
+        let (objective_value, shadow_prices) = if status == SolveStatus::Optimal {
+            let objective_value = self
+                .variables
+                .iter()
+                .zip(&self.columns)
+                .filter_map(|(variable, &(column, negative))| {
+                    let cost = self.objective_coefficients.get(variable)?;
+                    let value = match negative {
+                        Some(negative) => &column_values[column] - &column_values[negative],
+                        None => column_values[column].clone(),
+                    };
+                    Some(cost * value)
+                })
+                .fold(self.constant.clone(), |sum, value| sum + value);
+            // the entry of the objective row under the unit column of a row
+            // is its dual, for the row as turned around and the objective as
+            // maximised. A variable left unsplit has the reduced cost of being
+            // held at zero, which is owed to the constraint holding it there
+            let mut duals = self.unit_columns.iter().map(|&column| self.objective[column].clone()).collect::<Vec<_>>();
+            for (&(column, _), sign_row) in self.columns.iter().zip(&self.sign_rows) {
+                if let Some((row, coefficient)) = sign_row {
+                    let coefficient = if self.flipped[*row] { -coefficient } else { coefficient.clone() };
+                    duals[*row] -= &self.objective[column] / coefficient;
+                }
+            }
+            let shadow_prices = duals
+                .into_iter()
+                .zip(&self.flipped)
+                .map(|(dual, &flipped)| {
+                    if flipped != self.maximize {
+                        dual
+                    } else {
+                        -dual
+                    }
+                })
+                .collect();
+            (Some(objective_value), shadow_prices)
+        } else {
+            (None, vec![])
+        };
+
+        ExactSolution {
+            status,
+            objective_value,
+            values,
+            shadow_prices,
+            iterations: self.iterations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constraint, Objective};
+
+    fn fraction(numerator: i64, denominator: i64) -> BigRational {
+        BigRational::new(numerator.into(), denominator.into())
+    }
+
+    #[test]
+    fn test_solves_exactly() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let z = Variable::new("z");
+        // 0.1x + 0.2x is 0.30000000000000004x in floating point, which
+        // leaves x just short of one
+        let program = LinearProgram {
+            objective: Objective::new((1.0 * x) + (1.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (0.1 * x) + (0.2 * x), operator: Operator::LTE, rhs: Expression::zero() + 0.3 },
+                Constraint { lhs: (1.0 * x) + (3.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 2.0 },
+            ],
+        };
+        let solution = ExactSimplex::new(&program).solve();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.values[&x], fraction(1, 1));
+        assert_eq!(solution.values[&y], fraction(1, 3));
+        assert_eq!(solution.objective_value, Some(fraction(4, 3)));
+        let expected = [fraction(0, 1), fraction(0, 1), fraction(20, 9), fraction(1, 3)];
+        assert_eq!(solution.shadow_prices, expected);
+
+        // a free variable held by an equality and a >= constraint turned
+        // around by its negative right hand side, minimised
+        let program = LinearProgram {
+            objective: Objective::new((1.0 * x) + (2.0 * z) + 1.0, false),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (1.0 * x) + (1.0 * z), operator: Operator::Eq, rhs: Expression::zero() + 1.0 },
+                Constraint { lhs: z.into(), operator: Operator::GTE, rhs: Expression::zero() - 2.0 },
+            ],
+        };
+        let solution = ExactSimplex::new(&program).solve();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.values[&x], fraction(3, 1));
+        assert_eq!(solution.values[&z], fraction(-2, 1));
+        assert_eq!(solution.objective_value, Some(fraction(0, 1)));
+        assert_eq!(solution.shadow_prices, [fraction(0, 1), fraction(1, 1), fraction(1, 1)]);
+
+        // x is held at zero by the constraint that saves splitting it, which
+        // takes on its reduced cost
+        let program = LinearProgram {
+            objective: Objective::new((1.0 * y) + (-2.0 * x), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (1.0 * y) + (-1.0 * x), operator: Operator::LTE, rhs: Expression::zero() + 1.0 },
+                Constraint { lhs: y.into(), operator: Operator::LTE, rhs: Expression::zero() + 3.0 },
+            ],
+        };
+        let solution = ExactSimplex::new(&program).solve();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.objective_value, Some(fraction(1, 1)));
+        assert_eq!(solution.shadow_prices, [fraction(-1, 1), fraction(1, 1), fraction(0, 1)]);
+    }
+
+    #[test]
+    fn test_solves_programs_over_fractions() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let [one, third] = [fraction(1, 1), fraction(1, 3)];
+        let program = LinearProgram {
+            objective: Objective::new((one.clone() * x) + (one.clone() * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint {
+                    lhs: (third.clone() * x) + (one.clone() * y),
+                    operator: Operator::LTE,
+                    rhs: Expression::zero() + one.clone(),
+                },
+                Constraint { lhs: (one * x) + (third * y), operator: Operator::LTE, rhs: Expression::zero() + fraction(1, 1) },
+            ],
+        };
+        let solution = ExactSimplex::new(&program).solve();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.values[&x], fraction(3, 4));
+        assert_eq!(solution.values[&y], fraction(3, 4));
+        assert_eq!(solution.objective_value, Some(fraction(3, 2)));
+    }
+
+    #[test]
+    fn test_infeasible_and_unbounded() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let program = LinearProgram {
+            objective: Objective::new((1.0 * x) + (1.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: (1.0 * x) + (1.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 1.0 },
+                Constraint { lhs: (1.0 * x) + (1.0 * y), operator: Operator::GTE, rhs: Expression::zero() + 2.0 },
+            ],
+        };
+        let solution = ExactSimplex::new(&program).solve();
+        assert_eq!(solution.status, SolveStatus::Infeasible);
+        assert!(solution.values.is_empty());
+        assert_eq!(solution.objective_value, None);
+
+        let program = LinearProgram {
+            objective: Objective::new((1.0 * x) + (1.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (1.0 * x) + (-1.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 1.0 },
+            ],
+        };
+        let solution = ExactSimplex::new(&program).solve();
+        assert_eq!(solution.status, SolveStatus::Unbounded);
+    }
+}
@@ -0,0 +1,100 @@
+use super::{
+    basis_representation::{BasisRepresentation, SingularBasis},
+    lu_factorization::LuFactorization,
+    sparse_matrix::SparseMatrix,
+};
+
+// The number of eta matrices kept before the basis is factorized again, as
+// each one makes solving slower and lets more round-off in
+const REFACTORIZATION_INTERVAL: usize = 64;
+
+// The basis of the revised simplex method, which keeps the constraint matrix
+// as it was given and an LU factorization of the basis in place of the
+// tableau, working out only the parts of the tableau each pivot needs
+pub struct FactorizedBasis {
+    matrix: SparseMatrix,
+    costs: Vec<f64>,
+    basis: Vec<usize>,
+    factorization: LuFactorization,
+}
+
+impl FactorizedBasis {
+    fn factorize(&self) -> Result<LuFactorization, SingularBasis> {
+        let columns = self
+            .basis
+            .iter()
+            .map(|&column| self.matrix.column(column))
+            .collect::<Vec<_>>();
+        LuFactorization::new(self.matrix.num_rows(), &columns)
+    }
+}
+
+impl BasisRepresentation for FactorizedBasis {
+    fn new(matrix: SparseMatrix, basis: Vec<usize>) -> Self {
+        let basic_columns = basis.iter().map(|&column| matrix.column(column)).collect::<Vec<_>>();
+        // the starting basis is required to be nonsingular
+        let factorization = LuFactorization::new(matrix.num_rows(), &basic_columns).unwrap();
+        FactorizedBasis {
+            costs: vec![0.0; matrix.num_columns()],
+            matrix,
+            basis,
+            factorization,
+        }
+    }
+
+    fn basis(&self) -> &[usize] {
+        &self.basis
+    }
+
+    fn set_costs(&mut self, costs: &[f64]) {
+        self.costs = costs.to_vec();
+    }
+
+    fn reduced_costs(&mut self) -> Vec<f64> {
+        let duals = self.duals();
+        self.matrix.transpose_product(&duals)
+            .into_iter()
+            .zip(&self.costs)
+            .map(|(price, cost)| cost - price)
+            .collect()
+    }
+
+    fn duals(&mut self) -> Vec<f64> {
+        let basic_costs = self.basis.iter().map(|&column| self.costs[column]).collect::<Vec<_>>();
+        self.factorization.btran(&basic_costs)
+    }
+
+    fn column(&mut self, column: usize) -> Vec<f64> {
+        let mut vector = vec![0.0; self.matrix.num_rows()];
+        for &(row, value) in self.matrix.column(column) {
+            vector[row] = value;
+        }
+        self.factorization.ftran(&vector)
+    }
+
+    fn row(&mut self, position: usize) -> Vec<f64> {
+        let mut unit = vec![0.0; self.matrix.num_rows()];
+        unit[position] = 1.0;
+        let row = self.factorization.btran(&unit);
+        self.matrix.transpose_product(&row)
+    }
+
+    fn ftran(&mut self, vector: &[f64]) -> Vec<f64> {
+        self.factorization.ftran(vector)
+    }
+
+    fn column_products(&mut self, vector: &[f64]) -> Vec<f64> {
+        let product = self.factorization.btran(vector);
+        self.matrix.transpose_product(&product)
+    }
+
+    fn pivot(&mut self, position: usize, column: usize, alpha: &[f64]) -> Result<(), SingularBasis> {
+        self.basis[position] = column;
+        if self.factorization.updates() + 1 >= REFACTORIZATION_INTERVAL {
+            self.factorization = self.factorize()?;
+            Ok(())
+        } else {
+            self.factorization.update(position, alpha)
+        }
+    }
+}
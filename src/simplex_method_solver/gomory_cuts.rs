@@ -0,0 +1,270 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    lp_structs::VariableContainer, Constraint, Expression, LinearProgram, Operator, SimplexMethod, Solution,
+    SolverOptions, Variable,
+};
+
+use super::{
+    basis_representation::BasisRepresentation, factorized_basis::FactorizedBasis, simplex_tableau::SimplexTableau,
+    standard_form::StandardForm,
+};
+
+// How far from an integer the value of a basic variable has to be for its
+// row to give a cut. Rows of values closer than this give cuts with very
+// large coefficients that mostly cut off round-off
+const MIN_FRACTIONALITY: f64 = 1e-3;
+
+// The largest ratio between the largest and smallest coefficient a cut may
+// have before it is dropped as too badly scaled to trust
+const MAX_DYNAMISM: f64 = 1e6;
+
+// How a nonbasic column sits in the optimal tableau: the distance of the
+// column from the bound it is at, which is nonnegative, stands in for it
+struct Nonbasic {
+    at_upper: bool,
+    bound: f64,
+    integer: bool,
+}
+
+// Finds the Gomory mixed-integer cuts of the optimal tableau of a relaxation
+// whose solution is given, one for every integer variable that is basic at a
+// fractional value. Writing the row of such a variable x_i in terms of the
+// distances t_j of the nonbasic columns from their bounds gives
+// x_i + sum a_j t_j = b with b fractional, and rounding that row in the
+// mixed-integer rounding way gives sum g_j t_j >= 1, where with f_0 the
+// fractional part of b and f_j that of a_j
+//   g_j = f_j / f_0                  for integer t_j with f_j <= f_0
+//   g_j = (1 - f_j) / (1 - f_0)      for integer t_j with f_j > f_0
+//   g_j = a_j / f_0                  for continuous t_j with a_j >= 0
+//   g_j = -a_j / (1 - f_0)           for continuous t_j with a_j < 0
+// which holds for every solution where the integer variables are integers
+// and not for the one given, where every t_j is zero. When every variable in
+// the row is an integer it is the fractional cut strengthened. The slack of a
+// row is an integer when the row only has integer variables with integer
+// coefficients and right hand side. The cuts are given in the variables of
+// the program by putting the slacks back in terms of their rows, and only
+// hold for the bounds the program has, so cuts from a program with branches
+// added only hold below that node
+pub fn gomory_cuts<'a>(
+    program: &LinearProgram<'a>,
+    integer_variables: &[Variable<'a>],
+    solution: &Solution<'a>,
+    options: &SolverOptions,
+) -> Vec<Constraint<'a>> {
+    match options.simplex_method {
+        SimplexMethod::Tableau => cuts_from_tableau::<SimplexTableau>(program, integer_variables, solution, options),
+        SimplexMethod::Revised => cuts_from_tableau::<FactorizedBasis>(program, integer_variables, solution, options),
+    }
+}
+
+fn cuts_from_tableau<'a, B: BasisRepresentation>(
+    program: &LinearProgram<'a>,
+    integer_variables: &[Variable<'a>],
+    solution: &Solution<'a>,
+    options: &SolverOptions,
+) -> Vec<Constraint<'a>> {
+    let Some(basis) = &solution.basis else {
+        return vec![];
+    };
+    // the basis is the same whether or not the program was scaled, and the
+    // cuts are wanted for the program as given
+    let form = StandardForm::new(program, &SolverOptions { scaling: false, ..options.clone() });
+    let index = form
+        .variables
+        .iter()
+        .enumerate()
+        .map(|(column, var)| (*var, column))
+        .collect::<HashMap<_, _>>();
+    let columns = basis.variables.iter().filter_map(|var| index.get(var).copied()).collect::<Vec<_>>();
+    if form.infeasible || columns.len() != form.matrix.num_rows() {
+        return vec![];
+    }
+
+    let integers = integer_variables.iter().collect::<HashSet<_>>();
+    let structural = program.variables_iter().collect::<HashSet<_>>();
+    let is_integral = |value: f64| (value - value.round()).abs() <= options.integrality_tolerance;
+
+    // the row every slack belongs to and its coefficient there
+    let slacks = (0..form.variables.len())
+        .filter(|&column| !structural.contains(&form.variables[column]))
+        .map(|column| (column, form.matrix.column(column)[0]))
+        .collect::<Vec<_>>();
+
+    let mut values = form.variables.iter().map(|var| solution.value(var).unwrap_or(0.0)).collect::<Vec<_>>();
+    for &(column, (row, sign)) in &slacks {
+        let activity = form
+            .matrix
+            .row(row)
+            .iter()
+            .filter(|&&(other, _)| other != column)
+            .map(|&(other, value)| value * values[other])
+            .sum::<f64>();
+        values[column] = (form.rhs[row] - activity) / sign;
+    }
+
+    let mut is_integer = form.variables.iter().map(|var| integers.contains(var)).collect::<Vec<_>>();
+    for &(column, (row, _)) in &slacks {
+        is_integer[column] = is_integral(form.rhs[row])
+            && form
+                .matrix
+                .row(row)
+                .iter()
+                .filter(|&&(other, _)| other != column)
+                .all(|&(other, value)| integers.contains(&form.variables[other]) && is_integral(value));
+    }
+
+    let mut representation = B::new(form.matrix.clone(), columns);
+    let positions = representation.basis().to_vec();
+    let is_basic = {
+        let mut is_basic = vec![false; form.variables.len()];
+        positions.iter().for_each(|&column| is_basic[column] = true);
+        is_basic
+    };
+    let nonbasic = (0..form.variables.len())
+        .map(|column| {
+            if is_basic[column] {
+                return None;
+            }
+            let (value, lower, upper) = (values[column], form.lower[column], form.upper[column]);
+            let (at_upper, bound) = if (value - lower).abs() <= options.primal_feasibility_tolerance {
+                (false, lower)
+            } else if (value - upper).abs() <= options.primal_feasibility_tolerance {
+                (true, upper)
+            } else {
+                return None;
+            };
+            Some(Nonbasic { at_upper, bound, integer: is_integer[column] && is_integral(bound) })
+        })
+        .collect::<Vec<_>>();
+
+    let mut cuts = vec![];
+    for (position, &column) in positions.iter().enumerate() {
+        let value = values[column];
+        let f0 = value - value.floor();
+        if !integers.contains(&form.variables[column]) || !(MIN_FRACTIONALITY..=1.0 - MIN_FRACTIONALITY).contains(&f0) {
+            continue;
+        }
+        let row = representation.row(position);
+        let cut = round_row(&row, &is_basic, &nonbasic, f0, options.pivot_tolerance)
+            .and_then(|(coefficients, rhs)| in_program_variables(&form, &slacks, coefficients, rhs));
+        if let Some(cut) = cut {
+            cuts.push(cut);
+        }
+    }
+    cuts
+}
+
+// Rounds the tableau row of a basic variable with fractional part f0 into a
+// cut in the columns of the standard form, or None when a column moved off
+// its bounds keeps the row from giving one
+fn round_row(
+    row: &[f64],
+    is_basic: &[bool],
+    nonbasic: &[Option<Nonbasic>],
+    f0: f64,
+    tolerance: f64,
+) -> Option<(HashMap<usize, f64>, f64)> {
+    let mut coefficients = HashMap::new();
+    // the cut starts out as sum g_j t_j >= 1
+    let mut rhs = 1.0;
+    for (column, &entry) in row.iter().enumerate() {
+        if is_basic[column] || entry.abs() <= tolerance {
+            continue;
+        }
+        let Nonbasic { at_upper, bound, integer } = nonbasic[column].as_ref()?;
+        let a = if *at_upper { -entry } else { entry };
+        let g = if *integer {
+            let f = a - a.floor();
+            if f <= f0 { f / f0 } else { (1.0 - f) / (1.0 - f0) }
+        } else if a >= 0.0 {
+            a / f0
+        } else {
+            -a / (1.0 - f0)
+        };
+        if g == 0.0 {
+            continue;
+        }
+        // t_j is x_j - l_j at a lower bound and u_j - x_j at an upper one
+        let sign = if *at_upper { -1.0 } else { 1.0 };
+        coefficients.insert(column, sign * g);
+        rhs += sign * g * bound;
+    }
+    Some((coefficients, rhs))
+}
+
+// Puts the cut in terms of the variables of the program, taking out every
+// slack s of a row a x + c s = b as (b - a x) / c
+fn in_program_variables<'a>(
+    form: &StandardForm<'a>,
+    slacks: &[(usize, (usize, f64))],
+    mut coefficients: HashMap<usize, f64>,
+    mut rhs: f64,
+) -> Option<Constraint<'a>> {
+    for &(column, (row, sign)) in slacks {
+        let Some(coefficient) = coefficients.remove(&column) else {
+            continue;
+        };
+        let multiple = coefficient / sign;
+        rhs -= multiple * form.rhs[row];
+        for &(other, value) in form.matrix.row(row).iter().filter(|&&(other, _)| other != column) {
+            *coefficients.entry(other).or_insert(0.0) -= multiple * value;
+        }
+    }
+
+    let mut columns = coefficients.into_iter().filter(|(_, coefficient)| *coefficient != 0.0).collect::<Vec<_>>();
+    columns.sort_by_key(|&(column, _)| column);
+    let (smallest, largest) = columns.iter().fold((f64::INFINITY, 0.0), |(smallest, largest): (f64, f64), (_, c)| {
+        (smallest.min(c.abs()), largest.max(c.abs()))
+    });
+    if columns.is_empty() || largest > MAX_DYNAMISM * smallest {
+        return None;
+    }
+    let mut lhs = Expression::zero();
+    for (column, coefficient) in columns {
+        lhs += coefficient * form.variables[column];
+    }
+    Some(Constraint { lhs, operator: Operator::GTE, rhs: Expression::zero() + rhs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{simplex_method_solver, Evaluable, Objective};
+
+    #[test]
+    fn test_cuts_off_the_fractional_optimum_only() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let bound = |variable: Variable<'static>, operator, rhs: f64| Constraint {
+            lhs: variable.into(),
+            operator,
+            rhs: Expression::zero() + rhs,
+        };
+        // a row with a continuous slack and one with an integer slack
+        let program = LinearProgram {
+            objective: Objective::new((5.0 * x) + (4.0 * y), true),
+            constraints: vec![
+                bound(x, Operator::GTE, 0.0),
+                bound(y, Operator::GTE, 0.0),
+                bound(y, Operator::LTE, 3.0),
+                Constraint { lhs: (6.0 * x) + (4.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 24.5 },
+                Constraint { lhs: (1.0 * x) + (2.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 6.0 },
+            ],
+        };
+        let solution = simplex_method_solver(&program).unwrap();
+        let cuts = gomory_cuts(&program, &[x, y], &solution, &SolverOptions::default());
+        assert!(!cuts.is_empty());
+        for cut in &cuts {
+            let lhs = cut.lhs.evaluate(&solution).unwrap();
+            assert!(lhs < cut.rhs.constant - 1e-6, "{} holds at the relaxed optimum", cut);
+            for (i, j) in (0..=5).flat_map(|i| (0..=3).map(move |j| (i, j))) {
+                let (i, j) = (i as f64, j as f64);
+                if 6.0 * i + 4.0 * j <= 24.5 && i + 2.0 * j <= 6.0 {
+                    let lhs = cut.lhs.terms.iter().map(|term| term.coefficient * if term.variable == x { i } else { j });
+                    assert!(lhs.sum::<f64>() >= cut.rhs.constant - 1e-9, "{} cuts off ({}, {})", cut, i, j);
+                }
+            }
+        }
+    }
+}
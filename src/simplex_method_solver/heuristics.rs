@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use crate::{
+    lp_structs::VariableContainer, Basis, Constraint, Expression, LinearProgram, Objective, Operator, Solution,
+    SolveError, SolveStatus, SolverOptions, Variable,
+};
+
+use super::solve;
+
+// The most relaxations the feasibility pump solves before giving up
+const PUMP_ROUNDS: usize = 50;
+
+// An integer solution a heuristic found, as the value of the objective there
+// and the value of every variable
+pub type Found<'a> = (f64, HashMap<Variable<'a>, f64>);
+
+// Searches for integer solutions of a program close to the optimum of its
+// relaxation, which gives branch and bound a solution to prune against long
+// before the search itself turns one up. The solutions found satisfy every
+// constraint of the program the heuristics were made for, and every
+// relaxation they solve is counted in the iterations
+pub struct Heuristics<'a, 'b> {
+    program: &'b LinearProgram<'a>,
+    integer_variables: &'b [Variable<'a>],
+    options: &'b SolverOptions,
+    pub iterations: usize,
+}
+
+fn is_integral(value: f64, tolerance: f64) -> bool {
+    (value - value.round()).abs() <= tolerance
+}
+
+// The coefficient of the variable in the constraint once it is moved to
+// read lhs - rhs against zero
+fn coefficient<'a>(c: &Constraint<'a>, variable: &Variable<'a>) -> f64 {
+    let sum = |expr: &Expression<'a>| {
+        expr.terms.iter().filter(|term| term.variable == *variable).map(|term| term.coefficient).sum::<f64>()
+    };
+    sum(&c.lhs) - sum(&c.rhs)
+}
+
+fn evaluate<'a>(expr: &Expression<'a>, values: &HashMap<Variable<'a>, f64>) -> f64 {
+    expr.constant
+        + expr
+            .terms
+            .iter()
+            .map(|term| term.coefficient * values.get(&term.variable).copied().unwrap_or(0.0))
+            .sum::<f64>()
+}
+
+impl<'a, 'b> Heuristics<'a, 'b> {
+    pub fn new(
+        program: &'b LinearProgram<'a>,
+        integer_variables: &'b [Variable<'a>],
+        options: &'b SolverOptions,
+    ) -> Heuristics<'a, 'b> {
+        Heuristics { program, integer_variables, options, iterations: 0 }
+    }
+
+    fn solve(&mut self, program: &LinearProgram<'a>, basis: Option<&Basis<'a>>) -> Result<Solution<'a>, SolveError> {
+        let solution = solve(program, self.options, basis)?;
+        self.iterations += solution.iterations;
+        Ok(solution)
+    }
+
+    // The program with the constraints added
+    fn with_constraints(&self, constraints: impl IntoIterator<Item = Constraint<'a>>) -> LinearProgram<'a> {
+        LinearProgram {
+            objective: self.program.objective.clone(),
+            constraints: self.program.constraints.iter().cloned().chain(constraints).collect(),
+        }
+    }
+
+    fn fractional(&self, values: &HashMap<Variable<'a>, f64>) -> impl Iterator<Item = (Variable<'a>, f64)> + '_ {
+        let values = self
+            .integer_variables
+            .iter()
+            .filter_map(|variable| values.get(variable).map(|&value| (*variable, value)))
+            .collect::<Vec<_>>();
+        values.into_iter().filter(|&(_, value)| !is_integral(value, self.options.integrality_tolerance))
+    }
+
+    fn is_feasible(&self, values: &HashMap<Variable<'a>, f64>) -> bool {
+        self.program.constraints.iter().all(|c| {
+            let (lhs, rhs) = (evaluate(&c.lhs, values), evaluate(&c.rhs, values));
+            let tolerance = self.options.primal_feasibility_tolerance * rhs.abs().max(1.0);
+            match c.operator {
+                Operator::LTE => lhs <= rhs + tolerance,
+                Operator::GTE => lhs >= rhs - tolerance,
+                Operator::Eq => (lhs - rhs).abs() <= tolerance,
+            }
+        })
+    }
+
+    fn found(&self, values: HashMap<Variable<'a>, f64>) -> Found<'a> {
+        (evaluate(&self.program.objective.expression, &values), values)
+    }
+
+    // Rounds every fractional integer variable the way no constraint can
+    // stop it from moving, or the way the objective favours when neither can
+    // be stopped, and to the nearest integer when both can. The continuous
+    // variables keep their values, so this only succeeds when the rounding
+    // keeps every constraint holding, but it needs no relaxation solved
+    pub fn simple_rounding(&self, values: &HashMap<Variable<'a>, f64>) -> Option<Found<'a>> {
+        let mut rounded = values.clone();
+        for (variable, value) in self.fractional(values) {
+            // the constraints that moving the variable down or up can break
+            let (mut down_locks, mut up_locks) = (0, 0);
+            for c in &self.program.constraints {
+                let a = coefficient(c, &variable);
+                match c.operator {
+                    _ if a == 0.0 => {}
+                    Operator::Eq => (down_locks, up_locks) = (down_locks + 1, up_locks + 1),
+                    Operator::LTE if a > 0.0 => up_locks += 1,
+                    Operator::GTE if a < 0.0 => up_locks += 1,
+                    _ => down_locks += 1,
+                }
+            }
+            let cost = self
+                .program
+                .objective
+                .expression
+                .terms
+                .iter()
+                .filter(|term| term.variable == variable)
+                .map(|term| term.coefficient)
+                .sum::<f64>();
+            let improves_up = (cost > 0.0) == self.program.objective.maximize;
+            let up = match (down_locks, up_locks) {
+                (0, 0) => improves_up,
+                (0, _) => false,
+                (_, 0) => true,
+                _ => value.round() > value,
+            };
+            rounded.insert(variable, if up { value.ceil() } else { value.floor() });
+        }
+        self.is_feasible(&rounded).then(|| self.found(rounded))
+    }
+
+    // Fixes the integer variable whose value is nearest an integer at that
+    // integer and solves the relaxation again, over and over, until every
+    // integer variable is an integer or the relaxation becomes infeasible.
+    // Each fix is one of the integer variables, so it takes at most as many
+    // relaxations as there are of them
+    pub fn fractional_diving(&mut self, solution: &Solution<'a>) -> Result<Option<Found<'a>>, SolveError> {
+        let mut fixed = vec![];
+        let mut solution = solution.clone();
+        loop {
+            let nearest = self
+                .fractional(&solution.values)
+                .min_by(|a, b| (a.1 - a.1.round()).abs().total_cmp(&(b.1 - b.1.round()).abs()));
+            let Some((variable, value)) = nearest else {
+                return Ok(Some(self.found(solution.values)));
+            };
+            if fixed.len() == self.integer_variables.len() {
+                return Ok(None);
+            }
+            let rhs = Expression::zero() + value.round();
+            fixed.push(Constraint { lhs: variable.into(), operator: Operator::Eq, rhs });
+            let program = self.with_constraints(fixed.iter().cloned());
+            solution = self.solve(&program, solution.basis.as_ref())?;
+            if solution.status != SolveStatus::Optimal {
+                return Ok(None);
+            }
+        }
+    }
+
+    // Alternates between rounding the integer variables of a solution of the
+    // relaxation and finding the solution of the relaxation nearest that
+    // rounding, measured by how far every integer variable is from its
+    // rounded value, until the two meet. When a rounding comes round again
+    // the pump has stalled, and the rounded values furthest from the
+    // solution are pushed to their other side to break out. Once they meet
+    // the integer variables are fixed at their values and the continuous ones
+    // are given their best values for the objective
+    pub fn feasibility_pump(&mut self, solution: &Solution<'a>) -> Result<Option<Found<'a>>, SolveError> {
+        let mut values = solution.values.clone();
+        let mut previous: Option<Vec<f64>> = None;
+        let mut basis = None;
+        let first_id = self.program.next_temp_id();
+        for _ in 0..PUMP_ROUNDS {
+            if self.fractional(&values).next().is_none() {
+                return self.fix_integers(&values);
+            }
+            let current = self.integer_variables.iter().map(|variable| values.get(variable).copied().unwrap_or(0.0));
+            let mut rounded = current.clone().map(f64::round).collect::<Vec<_>>();
+            if previous.as_ref() == Some(&rounded) {
+                let flips = (rounded.len() / 10).max(1);
+                let mut furthest = current.enumerate().collect::<Vec<_>>();
+                furthest.sort_by(|a, b| (b.1 - rounded[b.0]).abs().total_cmp(&(a.1 - rounded[a.0]).abs()));
+                for (index, value) in furthest.into_iter().take(flips) {
+                    rounded[index] += if value > rounded[index] { 1.0 } else { -1.0 };
+                }
+            }
+
+            // d >= x - r and d >= r - x hold d above the distance of x from r
+            let mut distance = Expression::zero();
+            let mut constraints = vec![];
+            for (index, (variable, target)) in self.integer_variables.iter().zip(&rounded).enumerate() {
+                let d = Variable::Temp { id: first_id + index };
+                distance += 1.0 * d;
+                constraints.push(Constraint {
+                    lhs: (1.0 * d) + (-1.0 * *variable),
+                    operator: Operator::GTE,
+                    rhs: Expression::zero() - *target,
+                });
+                constraints.push(Constraint {
+                    lhs: (1.0 * d) + (1.0 * *variable),
+                    operator: Operator::GTE,
+                    rhs: Expression::zero() + *target,
+                });
+            }
+            let program = LinearProgram {
+                objective: Objective::new(distance, false),
+                constraints: self.program.constraints.iter().cloned().chain(constraints).collect(),
+            };
+            let nearest = self.solve(&program, basis.as_ref())?;
+            if nearest.status != SolveStatus::Optimal {
+                return Ok(None);
+            }
+            basis = nearest.basis;
+            values = nearest.values;
+            previous = Some(rounded);
+        }
+        Ok(None)
+    }
+
+    // The best solution with the integer variables fixed at their values
+    // rounded to the nearest integers
+    fn fix_integers(&mut self, values: &HashMap<Variable<'a>, f64>) -> Result<Option<Found<'a>>, SolveError> {
+        let fixed = self
+            .integer_variables
+            .iter()
+            .filter_map(|variable| values.get(variable).map(|&value| (*variable, value.round())))
+            .map(|(variable, value)| Constraint {
+                lhs: variable.into(),
+                operator: Operator::Eq,
+                rhs: Expression::zero() + value,
+            })
+            .collect::<Vec<_>>();
+        let solution = self.solve(&self.with_constraints(fixed), None)?;
+        Ok((solution.status == SolveStatus::Optimal).then(|| self.found(solution.values)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplex_method_solver;
+
+    fn nonnegative<'a>(variables: &[Variable<'a>]) -> Vec<Constraint<'a>> {
+        variables
+            .iter()
+            .map(|variable| Constraint { lhs: (*variable).into(), operator: Operator::GTE, rhs: Expression::zero() })
+            .collect()
+    }
+
+    #[test]
+    fn test_heuristics_find_integer_solutions() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let integers = [x, y];
+        let options = SolverOptions::default();
+
+        // nothing stops x from rounding up
+        let mut constraints = nonnegative(&[x, y]);
+        constraints.push(Constraint { lhs: (2.0 * x) + (2.0 * y), operator: Operator::GTE, rhs: Expression::zero() + 3.0 });
+        let program = LinearProgram { objective: Objective::new((1.0 * x) + (1.0 * y), false), constraints };
+        let solution = simplex_method_solver(&program).unwrap();
+        let (objective_value, values) = Heuristics::new(&program, &integers, &options)
+            .simple_rounding(&solution.values)
+            .unwrap();
+        assert_eq!(objective_value, 2.0);
+        assert_eq!(values[&x] + values[&y], 2.0);
+
+        // the relaxation is optimal at x = 3, y = 1.5, and the integer
+        // optimum is 20
+        let mut constraints = nonnegative(&[x, y]);
+        constraints.push(Constraint { lhs: (6.0 * x) + (4.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 24.0 });
+        constraints.push(Constraint { lhs: (1.0 * x) + (2.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 6.0 });
+        let program = LinearProgram { objective: Objective::new((5.0 * x) + (4.0 * y), true), constraints };
+        let solution = simplex_method_solver(&program).unwrap();
+        let mut heuristics = Heuristics::new(&program, &integers, &options);
+        let dived = heuristics.fractional_diving(&solution).unwrap().unwrap();
+        let pumped = heuristics.feasibility_pump(&solution).unwrap().unwrap();
+        for (objective_value, values) in [dived, pumped] {
+            assert!(heuristics.is_feasible(&values));
+            assert!(values.values().all(|value| is_integral(*value, 1e-9)));
+            assert!(objective_value <= 20.0 + 1e-9);
+        }
+        assert!(heuristics.iterations > 0);
+    }
+}
@@ -0,0 +1,415 @@
+use std::time::Instant;
+
+use crate::{SolveStatus, SolverOptions};
+
+use super::{
+    cholesky_factorization::CholeskyFactorization,
+    sparse_matrix::SparseMatrix,
+    standard_form::{SimplexOutcome, StandardForm},
+};
+
+// The residuals and the duality gap, relative to the size of the program,
+// that count as having reached the optimum
+const OPTIMALITY_TOLERANCE: f64 = 1e-8;
+
+// The number of iterations after which the method gives up as stalled,
+// which a program it can solve takes nowhere near
+const MAX_ITERATIONS: usize = 200;
+
+// The fraction of the way to the nearest bound every step goes, which keeps
+// the iterates strictly inside their bounds
+const STEP_FRACTION: f64 = 0.995;
+
+// Added to the weight of every column in the normal equations, so that the
+// columns of free variables, which have no bounds to weigh them, have one
+const REGULARIZATION: f64 = 1e-8;
+
+// Iterates growing larger than this are taken to be running off to infinity,
+// as they do when the program is infeasible or unbounded
+const DIVERGENCE: f64 = 1e12;
+
+// Bounds closer together than this fix their variable at the lower one
+const FIXED_WIDTH: f64 = 1e-12;
+
+// Mehrotra's primal-dual interior point method, run on the standard form
+// turned into minimise c . x subject to A x = b and l <= x <= u with
+// c = -costs. The iterates keep x strictly between its bounds, with the
+// distances w_l = x - l and w_u = u - x, and the duals z_l and z_u of the
+// bounds positive, while A x = b and the dual constraints
+// A^T y + z_l - z_u = c only come to hold at the optimum. Every iteration
+// takes a Newton step towards x_j w_j = mu for every bound, with mu shrinking
+// towards zero. An infinite bound has no dual, which is kept at zero along
+// with a distance of one so it drops out of every formula. A fixed variable
+// has no room between its bounds, so it is left out of the method, sitting
+// at its value with neither bound counted
+pub struct InteriorPoint<'f> {
+    matrix: &'f SparseMatrix,
+    rhs: &'f [f64],
+    costs: Vec<f64>,
+    lower: &'f [f64],
+    upper: &'f [f64],
+    has_lower: Vec<bool>,
+    has_upper: Vec<bool>,
+    fixed: Vec<bool>,
+    x: Vec<f64>,
+    y: Vec<f64>,
+    z_lower: Vec<f64>,
+    z_upper: Vec<f64>,
+    factorization: CholeskyFactorization,
+    iterations: usize,
+    iteration_limit: usize,
+    deadline: Option<Instant>,
+}
+
+// A step in every variable of the method
+struct Direction {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    z_lower: Vec<f64>,
+    z_upper: Vec<f64>,
+}
+
+fn largest_magnitude(values: &[f64]) -> f64 {
+    values.iter().fold(0.0, |largest, value| largest.max(value.abs()))
+}
+
+// The longest step, up to a whole one, that keeps every value positive, over
+// the values that have a bound
+fn step_to_boundary(values: &[f64], steps: &[f64], bounded: impl Fn(usize) -> bool) -> f64 {
+    (0..values.len())
+        .filter(|&index| bounded(index) && steps[index] < 0.0)
+        .map(|index| -values[index] / steps[index])
+        .fold(1.0, f64::min)
+}
+
+impl<'f> InteriorPoint<'f> {
+    // Starts with each variable a distance of one inside its bounds, or in
+    // the middle of them when they are closer together than that, and with
+    // every dual of a bound the size of the largest cost
+    pub fn new(form: &'f StandardForm, options: &SolverOptions) -> InteriorPoint<'f> {
+        let fixed = (0..form.lower.len())
+            .map(|column| form.upper[column] - form.lower[column] <= FIXED_WIDTH)
+            .collect::<Vec<_>>();
+        let has_lower = (0..form.lower.len())
+            .map(|column| form.lower[column].is_finite() && !fixed[column])
+            .collect::<Vec<_>>();
+        let has_upper = (0..form.upper.len())
+            .map(|column| form.upper[column].is_finite() && !fixed[column])
+            .collect::<Vec<_>>();
+        let x = (0..form.lower.len())
+            .map(|column| {
+                let (lower, upper) = (form.lower[column], form.upper[column]);
+                match (has_lower[column], has_upper[column]) {
+                    _ if fixed[column] => lower,
+                    (true, true) => lower + (upper - lower).min(2.0) / 2.0,
+                    (true, false) => lower + 1.0,
+                    (false, true) => upper - 1.0,
+                    (false, false) => 0.0,
+                }
+            })
+            .collect();
+        let dual = largest_magnitude(&form.costs).max(1.0);
+        let z_lower = has_lower.iter().map(|&bounded| if bounded { dual } else { 0.0 }).collect();
+        let z_upper = has_upper.iter().map(|&bounded| if bounded { dual } else { 0.0 }).collect();
+
+        let pattern = (0..form.matrix.num_rows())
+            .map(|row| {
+                let mut columns = form
+                    .matrix
+                    .row(row)
+                    .iter()
+                    .flat_map(|&(column, _)| form.matrix.column(column).iter().map(|&(other, _)| other))
+                    .collect::<Vec<_>>();
+                columns.sort_unstable();
+                columns.dedup();
+                columns
+            })
+            .collect::<Vec<_>>();
+
+        InteriorPoint {
+            matrix: &form.matrix,
+            rhs: &form.rhs,
+            costs: form.costs.iter().map(|cost| -cost).collect(),
+            lower: &form.lower,
+            upper: &form.upper,
+            has_lower,
+            has_upper,
+            fixed,
+            x,
+            y: vec![0.0; form.rhs.len()],
+            z_lower,
+            z_upper,
+            factorization: CholeskyFactorization::new(&pattern),
+            iterations: 0,
+            iteration_limit: options.iteration_limit,
+            deadline: form.deadline,
+        }
+    }
+
+    // The distances of x from its lower and upper bounds, one where the
+    // bound is infinite
+    fn distances(&self) -> (Vec<f64>, Vec<f64>) {
+        let w_lower = (0..self.x.len())
+            .map(|column| if self.has_lower[column] { self.x[column] - self.lower[column] } else { 1.0 })
+            .collect();
+        let w_upper = (0..self.x.len())
+            .map(|column| if self.has_upper[column] { self.upper[column] - self.x[column] } else { 1.0 })
+            .collect();
+        (w_lower, w_upper)
+    }
+
+    // b - A x and c - A^T y - z_l + z_u, where the dual constraint of a fixed
+    // variable always holds as the difference of its bounds' duals is free
+    fn residuals(&self) -> (Vec<f64>, Vec<f64>) {
+        let mut primal = self.rhs.to_vec();
+        for (column, &x) in self.x.iter().enumerate() {
+            for &(row, entry) in self.matrix.column(column) {
+                primal[row] -= entry * x;
+            }
+        }
+        let products = self.matrix.transpose_product(&self.y);
+        let dual = (0..self.x.len())
+            .map(|column| match self.fixed[column] {
+                true => 0.0,
+                false => self.costs[column] - products[column] - self.z_lower[column] + self.z_upper[column],
+            })
+            .collect();
+        (primal, dual)
+    }
+
+    // The average product of a bound's distance and its dual, mu
+    fn complementarity(&self, w_lower: &[f64], w_upper: &[f64], z_lower: &[f64], z_upper: &[f64]) -> f64 {
+        let count = self.has_lower.iter().chain(&self.has_upper).filter(|&&bounded| bounded).count();
+        if count == 0 {
+            return 0.0;
+        }
+        let products = (0..self.x.len())
+            .map(|column| w_lower[column] * z_lower[column] + w_upper[column] * z_upper[column])
+            .sum::<f64>();
+        products / count as f64
+    }
+
+    // c . x and b . y + l . z_l - u . z_u over the bounds, where a fixed
+    // variable's bounds have the duals that make up its dual constraint
+    fn objectives(&self) -> (f64, f64) {
+        let primal = self.costs.iter().zip(&self.x).map(|(cost, x)| cost * x).sum::<f64>();
+        let products = self.matrix.transpose_product(&self.y);
+        let bounds = (0..self.x.len())
+            .map(|column| {
+                if self.fixed[column] {
+                    return (self.costs[column] - products[column]) * self.lower[column];
+                }
+                let lower = if self.has_lower[column] { self.lower[column] * self.z_lower[column] } else { 0.0 };
+                let upper = if self.has_upper[column] { self.upper[column] * self.z_upper[column] } else { 0.0 };
+                lower - upper
+            })
+            .sum::<f64>();
+        let dual = self.rhs.iter().zip(&self.y).map(|(b, y)| b * y).sum::<f64>() + bounds;
+        (primal, dual)
+    }
+
+    // A diag(weights) A^T by row, the matrix of the normal equations
+    fn normal_matrix(&self, weights: &[f64]) -> Vec<Vec<(usize, f64)>> {
+        let rows = self.matrix.num_rows();
+        let mut work = vec![0.0; rows];
+        let mut touched = vec![false; rows];
+        (0..rows)
+            .map(|row| {
+                let mut entries = vec![];
+                for &(column, value) in self.matrix.row(row) {
+                    let factor = weights[column] * value;
+                    for &(other, entry) in self.matrix.column(column) {
+                        if !touched[other] {
+                            touched[other] = true;
+                            entries.push(other);
+                        }
+                        work[other] += factor * entry;
+                    }
+                }
+                entries
+                    .into_iter()
+                    .map(|other| {
+                        touched[other] = false;
+                        (other, std::mem::take(&mut work[other]))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Solves the Newton system for the residuals given, where r_l and r_u
+    // are what x_j w_j should change by for every bound. Eliminating the
+    // duals of the bounds leaves A^T dy - D dx = r with D the weight of every
+    // column, and eliminating dx then leaves the normal equations
+    // A D^-1 A^T dy = r_p + A D^-1 r, which the factorization solves
+    fn direction(
+        &self,
+        weights: &[f64],
+        (w_lower, w_upper): (&[f64], &[f64]),
+        (primal, dual): (&[f64], &[f64]),
+        (r_lower, r_upper): (&[f64], &[f64]),
+    ) -> Direction {
+        let columns = self.x.len();
+        let reduced = (0..columns)
+            .map(|column| dual[column] - r_lower[column] / w_lower[column] + r_upper[column] / w_upper[column])
+            .collect::<Vec<_>>();
+        let mut rhs = primal.to_vec();
+        for column in 0..columns {
+            let factor = weights[column] * reduced[column];
+            for &(row, entry) in self.matrix.column(column) {
+                rhs[row] += entry * factor;
+            }
+        }
+        let y = self.factorization.solve(&rhs);
+        let products = self.matrix.transpose_product(&y);
+        let x = (0..columns)
+            .map(|column| weights[column] * (products[column] - reduced[column]))
+            .collect::<Vec<f64>>();
+        let z_lower = (0..columns)
+            .map(|column| (r_lower[column] - self.z_lower[column] * x[column]) / w_lower[column])
+            .collect();
+        let z_upper = (0..columns)
+            .map(|column| (r_upper[column] + self.z_upper[column] * x[column]) / w_upper[column])
+            .collect();
+        Direction { x, y, z_lower, z_upper }
+    }
+
+    // The longest steps up to a whole one that keep the distances and the
+    // duals of the bounds positive, for x and for the duals
+    fn step_lengths(&self, w_lower: &[f64], w_upper: &[f64], direction: &Direction) -> (f64, f64) {
+        let minus_x = direction.x.iter().map(|step| -step).collect::<Vec<_>>();
+        let primal = step_to_boundary(w_lower, &direction.x, |column| self.has_lower[column])
+            .min(step_to_boundary(w_upper, &minus_x, |column| self.has_upper[column]));
+        let dual = step_to_boundary(&self.z_lower, &direction.z_lower, |column| self.has_lower[column])
+            .min(step_to_boundary(&self.z_upper, &direction.z_upper, |column| self.has_upper[column]));
+        (primal, dual)
+    }
+
+    fn has_converged(&self, primal: &[f64], dual: &[f64]) -> bool {
+        let (primal_objective, dual_objective) = self.objectives();
+        largest_magnitude(primal) <= OPTIMALITY_TOLERANCE * (1.0 + largest_magnitude(self.rhs))
+            && largest_magnitude(dual) <= OPTIMALITY_TOLERANCE * (1.0 + largest_magnitude(&self.costs))
+            && (primal_objective - dual_objective).abs() <= OPTIMALITY_TOLERANCE * (1.0 + primal_objective.abs())
+    }
+
+    fn has_diverged(&self) -> bool {
+        [&self.x, &self.y, &self.z_lower, &self.z_upper]
+            .iter()
+            .any(|values| values.iter().any(|value| !value.is_finite() || value.abs() > DIVERGENCE))
+    }
+
+    // Each iteration first takes the affine scaling step straight for the
+    // optimum, and from how far that gets it picks how much to aim for the
+    // central path instead, sigma = (mu_aff / mu)^3. The corrector step then
+    // aims for sigma mu along with making up for the second order terms the
+    // affine step left out. Gives up when the iterates run off or stall,
+    // which is how infeasible and unbounded programs show up, and stops
+    // where it is once the iteration or time limit has been reached
+    fn iterate(&mut self) -> Option<SolveStatus> {
+        while self.iterations < MAX_ITERATIONS {
+            let (w_lower, w_upper) = self.distances();
+            let (primal, dual) = self.residuals();
+            if self.has_converged(&primal, &dual) {
+                return Some(SolveStatus::Optimal);
+            }
+            if self.has_diverged() {
+                return None;
+            }
+            if self.iterations >= self.iteration_limit {
+                return Some(SolveStatus::IterationLimit);
+            }
+            if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Some(SolveStatus::TimeLimit);
+            }
+
+            // a fixed variable has no weight, so it never moves
+            let weights = (0..self.x.len())
+                .map(|column| match self.fixed[column] {
+                    true => 0.0,
+                    false => {
+                        1.0 / (self.z_lower[column] / w_lower[column]
+                            + self.z_upper[column] / w_upper[column]
+                            + REGULARIZATION)
+                    }
+                })
+                .collect::<Vec<_>>();
+            self.factorization.factorize(&self.normal_matrix(&weights));
+
+            let mu = self.complementarity(&w_lower, &w_upper, &self.z_lower, &self.z_upper);
+            let r_lower = (0..self.x.len()).map(|j| -w_lower[j] * self.z_lower[j]).collect::<Vec<_>>();
+            let r_upper = (0..self.x.len()).map(|j| -w_upper[j] * self.z_upper[j]).collect::<Vec<_>>();
+            let affine = self.direction(&weights, (&w_lower, &w_upper), (&primal, &dual), (&r_lower, &r_upper));
+            let (primal_step, dual_step) = self.step_lengths(&w_lower, &w_upper, &affine);
+
+            let moved = |values: &[f64], steps: &[f64], length: f64| {
+                values.iter().zip(steps).map(|(value, step)| value + length * step).collect::<Vec<_>>()
+            };
+            let minus_x = affine.x.iter().map(|step| -step).collect::<Vec<_>>();
+            let mu_affine = self.complementarity(
+                &moved(&w_lower, &affine.x, primal_step),
+                &moved(&w_upper, &minus_x, primal_step),
+                &moved(&self.z_lower, &affine.z_lower, dual_step),
+                &moved(&self.z_upper, &affine.z_upper, dual_step),
+            );
+            let sigma = if mu > 0.0 { (mu_affine / mu).powi(3).min(1.0) } else { 0.0 };
+
+            let r_lower = (0..self.x.len())
+                .map(|j| match self.has_lower[j] {
+                    true => sigma * mu - w_lower[j] * self.z_lower[j] - affine.x[j] * affine.z_lower[j],
+                    false => 0.0,
+                })
+                .collect::<Vec<_>>();
+            let r_upper = (0..self.x.len())
+                .map(|j| match self.has_upper[j] {
+                    true => sigma * mu - w_upper[j] * self.z_upper[j] + affine.x[j] * affine.z_upper[j],
+                    false => 0.0,
+                })
+                .collect::<Vec<_>>();
+            let step = self.direction(&weights, (&w_lower, &w_upper), (&primal, &dual), (&r_lower, &r_upper));
+            let (primal_step, dual_step) = self.step_lengths(&w_lower, &w_upper, &step);
+            let (primal_step, dual_step) = (STEP_FRACTION * primal_step, STEP_FRACTION * dual_step);
+
+            for (x, dx) in self.x.iter_mut().zip(&step.x) {
+                *x += primal_step * dx;
+            }
+            for (y, dy) in self.y.iter_mut().zip(&step.y) {
+                *y += dual_step * dy;
+            }
+            for (z, dz) in self.z_lower.iter_mut().zip(&step.z_lower) {
+                *z += dual_step * dz;
+            }
+            for (z, dz) in self.z_upper.iter_mut().zip(&step.z_upper) {
+                *z += dual_step * dz;
+            }
+            self.iterations += 1;
+        }
+        None
+    }
+
+    // The outcome in terms of the standard form, which maximises the costs,
+    // so the duals change sign and the reduced costs are z_u - z_l, or what
+    // is left of the cost of a fixed variable. There is no basis, as the
+    // optimum found can lie inside a face of optimal points. Nothing is
+    // returned when the method gives up
+    pub fn solve(mut self) -> Option<SimplexOutcome<'static>> {
+        let status = self.iterate()?;
+        let products = self.matrix.transpose_product(&self.y);
+        let reduced_costs = (0..self.x.len())
+            .map(|column| match self.fixed[column] {
+                true => products[column] - self.costs[column],
+                false => self.z_upper[column] - self.z_lower[column],
+            })
+            .collect();
+        Some(SimplexOutcome {
+            status,
+            duals: self.y.iter().map(|y| -y).collect(),
+            reduced_costs,
+            values: self.x,
+            ranging: None,
+            ray: vec![],
+            basis: None,
+            iterations: self.iterations,
+            trace: vec![],
+        })
+    }
+}
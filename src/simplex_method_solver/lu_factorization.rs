@@ -0,0 +1,231 @@
+use super::basis_representation::SingularBasis;
+
+// Pivots smaller than this in magnitude mean the basis is singular
+const SINGULARITY_TOLERANCE: f64 = 1e-11;
+
+// An LU factorization of the basis matrix followed by the eta matrices of
+// every change of basis made since, the product form of the inverse.
+//
+// Step k of the elimination pivots on row pivot_rows[k] of the basic column
+// at position pivot_positions[k]. lower[k] holds the multipliers of that step
+// by row, and upper[k] holds the entries above the diagonal of the same
+// column of U by step, with the diagonal itself in diagonal[k]
+pub struct LuFactorization {
+    pivot_rows: Vec<usize>,
+    pivot_positions: Vec<usize>,
+    lower: Vec<Vec<(usize, f64)>>,
+    upper: Vec<Vec<(usize, f64)>>,
+    diagonal: Vec<f64>,
+    etas: Vec<Eta>,
+}
+
+// A change of basis at a position, holding the entering column's entry at
+// that position and its entries at the other positions
+struct Eta {
+    position: usize,
+    pivot: f64,
+    entries: Vec<(usize, f64)>,
+}
+
+impl LuFactorization {
+    // Factorizes the basis from its columns, given as the nonzero entries of
+    // the column at every position
+    pub fn new(size: usize, columns: &[&[(usize, f64)]]) -> Result<LuFactorization, SingularBasis> {
+        let (factorization, dependent) = LuFactorization::eliminate(size, columns);
+        if dependent.is_empty() {
+            Ok(factorization)
+        } else {
+            Err(SingularBasis)
+        }
+    }
+
+    // Picks out as many independent columns as it can, giving the positions
+    // of those left out and the rows that no column was pivoted on
+    pub fn dependent_columns(rows: usize, columns: &[&[(usize, f64)]]) -> (Vec<usize>, Vec<usize>) {
+        let (factorization, dependent) = LuFactorization::eliminate(rows, columns);
+        let mut pivoted = vec![false; rows];
+        factorization.pivot_rows.iter().for_each(|&row| pivoted[row] = true);
+        let unpivoted = (0..rows).filter(|&row| !pivoted[row]).collect();
+        (dependent, unpivoted)
+    }
+
+    // Eliminates the sparsest columns first, pivoting on the largest remaining
+    // entry of each for stability, and skipping any column with no entry left
+    // to pivot on as it depends on those eliminated before it
+    fn eliminate(rows: usize, columns: &[&[(usize, f64)]]) -> (LuFactorization, Vec<usize>) {
+        let mut order = (0..columns.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&position| columns[position].len());
+
+        let mut factorization = LuFactorization {
+            pivot_rows: Vec::with_capacity(rows),
+            pivot_positions: Vec::with_capacity(rows),
+            lower: Vec::with_capacity(rows),
+            upper: Vec::with_capacity(rows),
+            diagonal: Vec::with_capacity(rows),
+            etas: vec![],
+        };
+        let mut dependent = vec![];
+        let mut pivoted = vec![false; rows];
+        let mut work = vec![0.0; rows];
+        for position in order {
+            for &(row, value) in columns[position] {
+                work[row] = value;
+            }
+            // apply the eliminations of the earlier steps to this column
+            let mut upper = vec![];
+            for earlier in 0..factorization.diagonal.len() {
+                let value = work[factorization.pivot_rows[earlier]];
+                if value != 0.0 {
+                    for &(row, multiplier) in &factorization.lower[earlier] {
+                        work[row] -= multiplier * value;
+                    }
+                    upper.push((earlier, value));
+                    work[factorization.pivot_rows[earlier]] = 0.0;
+                }
+            }
+
+            let pivot_row = (0..rows)
+                .filter(|&row| !pivoted[row])
+                .max_by(|&a, &b| work[a].abs().total_cmp(&work[b].abs()))
+                .filter(|&row| work[row].abs() >= SINGULARITY_TOLERANCE);
+            let Some(pivot_row) = pivot_row else {
+                dependent.push(position);
+                work.fill(0.0);
+                continue;
+            };
+            let pivot = work[pivot_row];
+            let lower = (0..rows)
+                .filter(|&row| row != pivot_row && work[row] != 0.0)
+                .map(|row| (row, work[row] / pivot))
+                .collect();
+            work.fill(0.0);
+
+            pivoted[pivot_row] = true;
+            factorization.pivot_rows.push(pivot_row);
+            factorization.pivot_positions.push(position);
+            factorization.lower.push(lower);
+            factorization.upper.push(upper);
+            factorization.diagonal.push(pivot);
+        }
+        (factorization, dependent)
+    }
+
+    // The number of changes of basis since the basis was factorized
+    pub fn updates(&self) -> usize {
+        self.etas.len()
+    }
+
+    // Records that the basic column at the position was replaced by one whose
+    // entries in terms of the old basis are alpha
+    pub fn update(&mut self, position: usize, alpha: &[f64]) -> Result<(), SingularBasis> {
+        let pivot = alpha[position];
+        if pivot.abs() < SINGULARITY_TOLERANCE {
+            return Err(SingularBasis);
+        }
+        let entries = alpha
+            .iter()
+            .enumerate()
+            .filter(|&(index, &value)| index != position && value != 0.0)
+            .map(|(index, &value)| (index, value))
+            .collect();
+        self.etas.push(Eta { position, pivot, entries });
+        Ok(())
+    }
+
+    // Solves B x = v, taking v by row and giving x by position
+    pub fn ftran(&self, vector: &[f64]) -> Vec<f64> {
+        let mut work = vector.to_vec();
+        for (step, lower) in self.lower.iter().enumerate() {
+            let value = work[self.pivot_rows[step]];
+            if value != 0.0 {
+                for &(row, multiplier) in lower {
+                    work[row] -= multiplier * value;
+                }
+            }
+        }
+
+        let mut result = vec![0.0; vector.len()];
+        for step in (0..self.diagonal.len()).rev() {
+            let value = work[self.pivot_rows[step]] / self.diagonal[step];
+            if value != 0.0 {
+                for &(earlier, entry) in &self.upper[step] {
+                    work[self.pivot_rows[earlier]] -= entry * value;
+                }
+            }
+            result[self.pivot_positions[step]] = value;
+        }
+
+        for eta in &self.etas {
+            let value = result[eta.position] / eta.pivot;
+            result[eta.position] = value;
+            if value != 0.0 {
+                for &(index, entry) in &eta.entries {
+                    result[index] -= entry * value;
+                }
+            }
+        }
+        result
+    }
+
+    // Solves B^T y = v, taking v by position and giving y by row
+    pub fn btran(&self, vector: &[f64]) -> Vec<f64> {
+        let mut work = vector.to_vec();
+        for eta in self.etas.iter().rev() {
+            let sum = eta.entries.iter().map(|&(index, entry)| entry * work[index]).sum::<f64>();
+            work[eta.position] = (work[eta.position] - sum) / eta.pivot;
+        }
+
+        // solve U^T z = v step by step, then L^T y = z back to front
+        let mut steps = vec![0.0; vector.len()];
+        for step in 0..self.diagonal.len() {
+            let sum = self.upper[step]
+                .iter()
+                .map(|&(earlier, entry)| entry * steps[earlier])
+                .sum::<f64>();
+            steps[step] = (work[self.pivot_positions[step]] - sum) / self.diagonal[step];
+        }
+        let mut result = vec![0.0; vector.len()];
+        for step in (0..self.diagonal.len()).rev() {
+            let sum = self.lower[step]
+                .iter()
+                .map(|&(row, multiplier)| multiplier * result[row])
+                .sum::<f64>();
+            result[self.pivot_rows[step]] = steps[step] - sum;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solves_after_updates() {
+        // B = [[2, 1, 0], [0, 3, 1], [4, 0, 5]] by columns
+        let columns: [&[(usize, f64)]; 3] = [
+            &[(0, 2.0), (2, 4.0)],
+            &[(0, 1.0), (1, 3.0)],
+            &[(1, 1.0), (2, 5.0)],
+        ];
+        let mut factorization = LuFactorization::new(3, &columns).unwrap();
+        let x = factorization.ftran(&[3.0, 4.0, 9.0]);
+        for (actual, expected) in x.iter().zip([1.0, 1.0, 1.0]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+
+        // replace the second column with (1, 1, 1), where B alpha = (1, 1, 1)
+        let alpha = factorization.ftran(&[1.0, 1.0, 1.0]);
+        factorization.update(1, &alpha).unwrap();
+        // the new B is [[2, 1, 0], [0, 1, 1], [4, 1, 5]]
+        let x = factorization.ftran(&[3.0, 2.0, 10.0]);
+        for (actual, expected) in x.iter().zip([1.0, 1.0, 1.0]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        // B^T y = (6, 3, 6) has solution y = (1, 1, 1)
+        let y = factorization.btran(&[6.0, 3.0, 6.0]);
+        for (actual, expected) in y.iter().zip([1.0, 1.0, 1.0]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+    }
+}
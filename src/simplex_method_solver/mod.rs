@@ -0,0 +1,1149 @@
+use crate::{
+    Algorithm, Basis, ExactSolution, Expression, InitialBasis, LinearProgram, MipSolution, MixedIntegerProgram,
+    Objective, RationalCoefficient, SimplexMethod, Solution, SolveError, SolveStatus, SolverOptions,
+};
+
+use self::{
+    branch_and_bound::BranchAndBound,
+    exact_simplex::ExactSimplex,
+    factorized_basis::FactorizedBasis,
+    interior_point::InteriorPoint,
+    simplex::Simplex,
+    simplex_tableau::SimplexTableau,
+    standard_form::{SimplexOutcome, StandardForm},
+};
+
+mod basis_representation;
+mod branch_and_bound;
+mod cholesky_factorization;
+mod exact_simplex;
+mod factorized_basis;
+mod gomory_cuts;
+mod heuristics;
+mod interior_point;
+mod lu_factorization;
+mod postsolve;
+mod presolve;
+mod pricing;
+mod program_transformer;
+mod scaling;
+mod simplex;
+mod simplex_tableau;
+mod sparse_matrix;
+mod standard_form;
+
+// Checks that every coefficient and constant in the program is finite
+fn validate<T: RationalCoefficient>(program: &LinearProgram<T>) -> Result<(), SolveError> {
+    fn is_finite<T: RationalCoefficient>(expr: &Expression<T>) -> bool {
+        expr.constant.is_finite() && expr.terms.iter().all(|term| term.coefficient.is_finite())
+    }
+
+    if !is_finite(&program.objective.expression) {
+        return Err(SolveError::NonFiniteObjective);
+    }
+    for (index, c) in program.constraints.iter().enumerate() {
+        if !is_finite(&c.lhs) || !is_finite(&c.rhs) {
+            return Err(SolveError::NonFiniteConstraint { index });
+        }
+    }
+    Ok(())
+}
+
+pub fn simplex_method_solver<'a>(program: &LinearProgram<'a>) -> Result<Solution<'a>, SolveError> {
+    simplex_method_solver_with_options(program, &SolverOptions::default())
+}
+
+pub fn simplex_method_solver_with_options<'a>(
+    program: &LinearProgram<'a>,
+    options: &SolverOptions,
+) -> Result<Solution<'a>, SolveError> {
+    solve(program, options, None)
+}
+
+// Solves the program starting from the basis of an earlier solution, which
+// after adding constraints or changing right hand sides is usually only a
+// few dual simplex pivots away from optimal
+pub fn simplex_method_solver_from_basis<'a>(
+    program: &LinearProgram<'a>,
+    options: &SolverOptions,
+    basis: &Basis<'a>,
+) -> Result<Solution<'a>, SolveError> {
+    solve(program, options, Some(basis))
+}
+
+// Solves the program in exact rational arithmetic, reading every f64
+// coefficient as the simplest fraction it is a rounding of, or taking the
+// fractions of a program built over BigRational as they are. The full tableau
+// is pivoted and its fractions can grow large, so this is meant for small
+// programs such as those used in teaching or checked for exact answers
+pub fn exact_simplex_method_solver<'a, T: RationalCoefficient>(
+    program: &LinearProgram<'a, T>,
+) -> Result<ExactSolution<'a>, SolveError> {
+    validate(program)?;
+    Ok(ExactSimplex::new(program).solve())
+}
+
+pub fn branch_and_bound_solver<'a>(mip: &MixedIntegerProgram<'a>) -> Result<MipSolution<'a>, SolveError> {
+    branch_and_bound_solver_with_options(mip, &SolverOptions::default())
+}
+
+// Solves a mixed-integer program by branch and bound over the relaxations
+// of the program, each solved by the method the options choose and started
+// from the basis its parent finished with
+pub fn branch_and_bound_solver_with_options<'a>(
+    mip: &MixedIntegerProgram<'a>,
+    options: &SolverOptions,
+) -> Result<MipSolution<'a>, SolveError> {
+    BranchAndBound::new(mip, options, true).solve()
+}
+
+pub fn cutting_plane_solver<'a>(mip: &MixedIntegerProgram<'a>) -> Result<MipSolution<'a>, SolveError> {
+    cutting_plane_solver_with_options(mip, &SolverOptions::default())
+}
+
+// Solves a mixed-integer program by adding Gomory cuts to its relaxation
+// until the relaxation is integer, without branching. When the rounds of
+// cuts the options allow are used up first, or no more cuts are found, the
+// solution is left at the iteration limit with the bound the cuts reached
+pub fn cutting_plane_solver_with_options<'a>(
+    mip: &MixedIntegerProgram<'a>,
+    options: &SolverOptions,
+) -> Result<MipSolution<'a>, SolveError> {
+    BranchAndBound::new(mip, options, false).solve()
+}
+
+fn solve<'a>(
+    program: &LinearProgram<'a>,
+    options: &SolverOptions,
+    warm_start: Option<&Basis<'a>>,
+) -> Result<Solution<'a>, SolveError> {
+    validate(program)?;
+    let mut form = StandardForm::new(program, options);
+    let mut outcome = if form.infeasible {
+        SimplexOutcome {
+            status: SolveStatus::Infeasible,
+            values: vec![],
+            duals: vec![],
+            reduced_costs: vec![],
+            ranging: None,
+            ray: vec![],
+            basis: None,
+            iterations: 0,
+            trace: vec![],
+        }
+    } else if options.algorithm == Algorithm::InteriorPoint {
+        match InteriorPoint::new(&form, options).solve() {
+            Some(outcome) if options.crossover && outcome.status == SolveStatus::Optimal => {
+                cross_over(&mut form, options, outcome)
+                    .unwrap_or_else(|| run_simplex(&mut form, options, None))
+            }
+            Some(outcome) => outcome,
+            None => run_simplex(&mut form, options, None),
+        }
+    } else {
+        run_simplex(&mut form, options, warm_start)
+    };
+
+    // the reductions presolving made are not carried into a proof of
+    // infeasibility, so it is found on the program as given instead
+    if outcome.status == SolveStatus::Infeasible && form.presolved {
+        return solve(program, &SolverOptions { presolve: false, ..options.clone() }, warm_start);
+    }
+
+    // the dual simplex method finds a row that cannot be made feasible
+    // rather than the phase I duals that prove it, so phase I is run from
+    // scratch to find them
+    if outcome.status == SolveStatus::Infeasible && outcome.duals.is_empty() && !form.infeasible {
+        let phase_one = SolverOptions {
+            algorithm: Algorithm::PrimalSimplex,
+            initial_basis: InitialBasis::TwoPhase,
+            record_trace: false,
+            ..options.clone()
+        };
+        let proof = run_simplex(&mut form, &phase_one, None);
+        if proof.status == SolveStatus::Infeasible {
+            outcome.values = proof.values;
+            outcome.duals = proof.duals;
+            outcome.reduced_costs = proof.reduced_costs;
+        }
+    }
+    form.into_solution(program, outcome)
+}
+
+fn run_simplex<'a>(
+    form: &mut StandardForm<'a>,
+    options: &SolverOptions,
+    warm_start: Option<&Basis<'a>>,
+) -> SimplexOutcome<'a> {
+    match options.simplex_method {
+        SimplexMethod::Tableau => Simplex::<SimplexTableau>::new(form, options, warm_start).solve(),
+        SimplexMethod::Revised => Simplex::<FactorizedBasis>::new(form, options, warm_start).solve(),
+    }
+}
+
+// Crosses over from the optimum of the interior point method to an optimal
+// basis, counting the iterations of both. Gives up if the simplex method
+// cannot finish from the basis found
+fn cross_over<'a>(
+    form: &mut StandardForm<'a>,
+    options: &SolverOptions,
+    interior: SimplexOutcome,
+) -> Option<SimplexOutcome<'a>> {
+    let mut outcome = match options.simplex_method {
+        SimplexMethod::Tableau => Simplex::<SimplexTableau>::crossover(form, options, &interior.values)?.solve(),
+        SimplexMethod::Revised => Simplex::<FactorizedBasis>::crossover(form, options, &interior.values)?.solve(),
+    };
+    outcome.iterations += interior.iterations;
+    (outcome.status == SolveStatus::Optimal).then_some(outcome)
+}
+
+// Finds an irreducible infeasible subset of the constraints of a program,
+// a set of them that cannot all hold together but can once any one of them
+// is left out, given by their indices in the program. The constraints with a
+// multiplier in the Farkas certificate already cannot all hold, and each of
+// them is then dropped for good if the rest stay infeasible without it.
+// Returns None when the program is not infeasible
+pub fn find_irreducible_infeasible_subset(
+    program: &LinearProgram,
+    options: &SolverOptions,
+) -> Result<Option<Vec<usize>>, SolveError> {
+    let options = SolverOptions { record_trace: false, ..options.clone() };
+    // only whether the constraints can hold matters, not the objective
+    let is_infeasible = |subset: &[usize]| {
+        let subprogram = LinearProgram {
+            objective: Objective::new(Expression::zero(), true),
+            constraints: subset.iter().map(|&index| program.constraints[index].clone()).collect(),
+        };
+        simplex_method_solver_with_options(&subprogram, &options)
+            .map(|solution| solution.status == SolveStatus::Infeasible)
+    };
+
+    let solution = simplex_method_solver_with_options(program, &options)?;
+    if solution.status != SolveStatus::Infeasible {
+        return Ok(None);
+    }
+    let mut subset = (0..program.constraints.len())
+        .filter(|&index| solution.farkas_certificate.get(index).map_or(true, |&y| y.abs() > options.dual_feasibility_tolerance))
+        .collect::<Vec<_>>();
+    if !is_infeasible(&subset)? {
+        subset = (0..program.constraints.len()).collect();
+    }
+
+    let mut position = 0;
+    while position < subset.len() {
+        let mut rest = subset.clone();
+        rest.remove(position);
+        if is_infeasible(&rest)? {
+            subset = rest;
+        } else {
+            position += 1;
+        }
+    }
+    Ok(Some(subset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Algorithm, Constraint, Evaluable, InitialBasis, NodeSelection, Objective, Operator, Phase, PivotRule,
+        SimplexMethod, SolveStatus, Variable,
+    };
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-6, "expected {} but got {}", expected, actual);
+    }
+
+    #[test]
+    fn test_maximize_with_upper_bounds() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let program = LinearProgram {
+            objective: Objective::new((3.0 * x) + (5.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: x.into(), operator: Operator::LTE, rhs: Expression::zero() + 4.0 },
+                Constraint { lhs: (2.0 * y).into(), operator: Operator::LTE, rhs: Expression::zero() + 12.0 },
+                Constraint { lhs: (3.0 * x) + (2.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 18.0 },
+            ],
+        };
+        let solution = simplex_method_solver(&program).unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_close(solution.objective_value, 36.0);
+        assert_close(solution.values[&x], 2.0);
+        assert_close(solution.values[&y], 6.0);
+        assert_close(program.objective.expression.evaluate(&solution).unwrap(), 36.0);
+    }
+
+    #[test]
+    fn test_minimize_with_shifted_and_free_variables() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let z = Variable::new("z");
+        // x has two lower bounds, y a negative one and z none at all
+        let program = LinearProgram {
+            objective: Objective::new((1.0 * x) + (1.0 * y) + (-1.0 * z), false),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (2.0 * x).into(), operator: Operator::GTE, rhs: Expression::zero() + 4.0 },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() - 3.0 },
+                Constraint { lhs: (1.0 * x) + (1.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 10.0 },
+                Constraint { lhs: z.into(), operator: Operator::LTE, rhs: Expression::zero() + 5.0 },
+            ],
+        };
+        let solution = simplex_method_solver(&program).unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_close(solution.objective_value, -6.0);
+        assert_close(solution.values[&x], 2.0);
+        assert_close(solution.values[&y], -3.0);
+        assert_close(solution.values[&z], 5.0);
+    }
+
+    #[test]
+    fn test_unbounded() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let z = Variable::new("z");
+        let program = LinearProgram {
+            objective: Objective::new((1.0 * x) + (1.0 * y) + (2.0 * z), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (1.0 * x) + (-1.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 1.0 },
+                Constraint { lhs: z.into(), operator: Operator::LTE, rhs: Expression::zero() + 5.0 },
+            ],
+        };
+        let solution = simplex_method_solver(&program).unwrap();
+        assert_eq!(solution.status, SolveStatus::Unbounded);
+
+        // z cannot grow, and stepping along the ray keeps every constraint
+        // holding while the objective grows
+        let ray = &solution.unbounded_ray;
+        assert_close(ray[&z], 0.0);
+        assert!(ray[&y] > 0.0);
+        let stepped = Solution {
+            values: solution.values.iter().map(|(var, value)| (*var, value + 100.0 * ray[var])).collect(),
+            ..solution.clone()
+        };
+        for c in &program.constraints {
+            let difference = c.rhs.evaluate(&stepped).unwrap() - c.lhs.evaluate(&stepped).unwrap();
+            match c.operator {
+                Operator::LTE => assert!(difference >= -1e-9),
+                Operator::GTE => assert!(difference <= 1e-9),
+                Operator::Eq => assert_close(difference, 0.0),
+            }
+        }
+        assert!(ray[&x] + ray[&y] + 2.0 * ray[&z] > 0.0);
+    }
+
+    #[test]
+    fn test_phase_one_with_surplus_and_equality_rows() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let program = LinearProgram {
+            objective: Objective::new((2.0 * x) + (3.0 * y), false),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (1.0 * x) + (1.0 * y), operator: Operator::GTE, rhs: Expression::zero() + 4.0 },
+                Constraint { lhs: (1.0 * x) + (3.0 * y), operator: Operator::GTE, rhs: Expression::zero() + 6.0 },
+                // a redundant copy of the rows above leaves an artificial variable basic
+                Constraint { lhs: (2.0 * x) + (2.0 * y), operator: Operator::Eq, rhs: Expression::zero() + 8.0 },
+                Constraint { lhs: (1.0 * x) + (1.0 * y), operator: Operator::Eq, rhs: Expression::zero() + 4.0 },
+            ],
+        };
+        let solution = simplex_method_solver(&program).unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_close(solution.objective_value, 9.0);
+        assert_close(solution.values[&x], 3.0);
+        assert_close(solution.values[&y], 1.0);
+    }
+
+    #[test]
+    fn test_big_m_matches_two_phase() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let program = LinearProgram {
+            objective: Objective::new((2.0 * x) + (3.0 * y), false),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (1.0 * x) + (1.0 * y), operator: Operator::GTE, rhs: Expression::zero() + 4.0 },
+                Constraint { lhs: (1.0 * x) + (3.0 * y), operator: Operator::Eq, rhs: Expression::zero() + 6.0 },
+            ],
+        };
+        let two_phase = SolverOptions { record_trace: true, ..SolverOptions::default() };
+        let big_m = SolverOptions {
+            initial_basis: InitialBasis::BigM(None),
+            record_trace: true,
+            ..SolverOptions::default()
+        };
+        let two_phase = simplex_method_solver_with_options(&program, &two_phase).unwrap();
+        let big_m = simplex_method_solver_with_options(&program, &big_m).unwrap();
+
+        assert_eq!(big_m.status, SolveStatus::Optimal);
+        assert_close(big_m.objective_value, two_phase.objective_value);
+        assert_close(big_m.values[&x], 3.0);
+        assert_close(big_m.values[&y], 1.0);
+        assert!(two_phase.trace.iter().any(|pivot| pivot.phase == Phase::One));
+        assert!(big_m.trace.iter().all(|pivot| pivot.phase == Phase::Two));
+        assert_eq!(big_m.trace.len(), big_m.iterations);
+    }
+
+    #[test]
+    fn test_interior_point() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let v = Variable::new("v");
+        let w = Variable::new("w");
+        // redundant equality rows, a fixed variable and a free one
+        let program = LinearProgram {
+            objective: Objective::new((2.0 * x) + (3.0 * y) + (1.0 * v) + (-1.0 * w), false),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (1.0 * x) + (3.0 * y), operator: Operator::GTE, rhs: Expression::zero() + 6.0 },
+                Constraint { lhs: (2.0 * x) + (2.0 * y), operator: Operator::Eq, rhs: Expression::zero() + 8.0 },
+                Constraint { lhs: (1.0 * x) + (1.0 * y), operator: Operator::Eq, rhs: Expression::zero() + 4.0 },
+                Constraint { lhs: v.into(), operator: Operator::GTE, rhs: Expression::zero() + 2.0 },
+                Constraint { lhs: v.into(), operator: Operator::LTE, rhs: Expression::zero() + 2.0 },
+                Constraint { lhs: (1.0 * w) + (-1.0 * x), operator: Operator::Eq, rhs: Expression::zero() + 1.0 },
+            ],
+        };
+        let options = SolverOptions {
+            algorithm: Algorithm::InteriorPoint,
+            crossover: false,
+            ..SolverOptions::default()
+        };
+        let solution = simplex_method_solver_with_options(&program, &options).unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert!(solution.basis.is_none());
+        assert_close(solution.objective_value, 7.0);
+        assert_close(solution.values[&x], 3.0);
+        assert_close(solution.values[&y], 1.0);
+        assert_close(solution.values[&v], 2.0);
+        assert_close(solution.values[&w], 4.0);
+
+        // crossing over finds a basis of the same optimum, keeping an
+        // artificial variable in one of the redundant rows
+        let options = SolverOptions { crossover: true, ..options };
+        let solution = simplex_method_solver_with_options(&program, &options).unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert!(solution.basis.is_some());
+        assert!(solution.sensitivity.is_some());
+        assert_close(solution.objective_value, 7.0);
+        assert_close(solution.values[&w], 4.0);
+
+        // without them the simplex method warm starts from it without pivoting
+        let mut constraints = program.constraints.clone();
+        constraints.remove(3);
+        let independent = LinearProgram { objective: program.objective.clone(), constraints };
+        let solution = simplex_method_solver_with_options(&independent, &options).unwrap();
+        let basis = solution.basis.unwrap();
+        let warm = simplex_method_solver_from_basis(&independent, &SolverOptions::default(), &basis).unwrap();
+        assert_eq!(warm.status, SolveStatus::Optimal);
+        assert_eq!(warm.iterations, 0);
+        assert_close(warm.objective_value, 7.0);
+
+        // programs the interior point method cannot solve are left to the
+        // simplex method, which still proves what it finds
+        let mut constraints = program.constraints.clone();
+        constraints.push(Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() + 5.0 });
+        let infeasible = LinearProgram { objective: program.objective.clone(), constraints };
+        let solution = simplex_method_solver_with_options(&infeasible, &options).unwrap();
+        assert_eq!(solution.status, SolveStatus::Infeasible);
+        assert_eq!(solution.farkas_certificate.len(), infeasible.constraints.len());
+
+        let unbounded = LinearProgram {
+            objective: Objective::new((1.0 * x) + (1.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (1.0 * x) + (-1.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 1.0 },
+            ],
+        };
+        let solution = simplex_method_solver_with_options(&unbounded, &options).unwrap();
+        assert_eq!(solution.status, SolveStatus::Unbounded);
+        assert!(solution.unbounded_ray[&y] > 0.0);
+    }
+
+    #[test]
+    fn test_interior_point_with_bounds_crossing_within_tolerance() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let program = LinearProgram {
+            objective: Objective::new((1.0 * x) + (1.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() + (1.0 + 1e-8) },
+                Constraint { lhs: x.into(), operator: Operator::LTE, rhs: Expression::zero() + 1.0 },
+                Constraint { lhs: (1.0 * x) + (1.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 3.0 },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+            ],
+        };
+        for algorithm in [Algorithm::PrimalSimplex, Algorithm::DualSimplex, Algorithm::InteriorPoint] {
+            let options = SolverOptions { algorithm, ..SolverOptions::default() };
+            let solution = simplex_method_solver_with_options(&program, &options).unwrap();
+            assert_eq!(solution.status, SolveStatus::Optimal);
+            assert_close(solution.objective_value, 3.0);
+            assert_close(solution.values[&x], 1.0);
+        }
+    }
+
+    #[test]
+    fn test_pivot_rules_on_beales_cycling_example() {
+        let [x1, x2, x3, x4] = ["x1", "x2", "x3", "x4"].map(Variable::new);
+        let mut constraints = [x1, x2, x3, x4]
+            .map(|x| Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() })
+            .to_vec();
+        constraints.extend([
+            Constraint {
+                lhs: (0.25 * x1) + (-8.0 * x2) + (-1.0 * x3) + (9.0 * x4),
+                operator: Operator::LTE,
+                rhs: Expression::zero(),
+            },
+            Constraint {
+                lhs: (0.5 * x1) + (-12.0 * x2) + (-0.5 * x3) + (3.0 * x4),
+                operator: Operator::LTE,
+                rhs: Expression::zero(),
+            },
+            Constraint { lhs: x3.into(), operator: Operator::LTE, rhs: Expression::zero() + 1.0 },
+        ]);
+        let program = LinearProgram {
+            objective: Objective::new((0.75 * x1) + (-20.0 * x2) + (0.5 * x3) + (-6.0 * x4), true),
+            constraints,
+        };
+        for pivot_rule in [PivotRule::Bland, PivotRule::SteepestEdge, PivotRule::Devex] {
+            let options = SolverOptions { pivot_rule, ..SolverOptions::default() };
+            let solution = simplex_method_solver_with_options(&program, &options).unwrap();
+            assert_eq!(solution.status, SolveStatus::Optimal, "{}", pivot_rule);
+            assert_close(solution.objective_value, 1.25);
+        }
+    }
+
+    #[test]
+    fn test_revised_matches_tableau_past_refactorization() {
+        // the Klee-Minty cube takes Dantzig's rule through all 128 of its
+        // vertices, so the revised method refactorizes its basis along the way
+        let names = (1..=7).map(|index| format!("x{}", index)).collect::<Vec<_>>();
+        let x = names.iter().map(|name| Variable::new(name)).collect::<Vec<_>>();
+        let mut constraints = x
+            .iter()
+            .map(|&x| Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() })
+            .collect::<Vec<_>>();
+        for i in 0..7 {
+            let lhs = (0..i).fold(Expression::from(x[i]), |lhs, j| {
+                lhs + (2.0f64.powi((i - j + 1) as i32) * x[j])
+            });
+            let rhs = Expression::zero() + 5.0f64.powi(i as i32 + 1);
+            constraints.push(Constraint { lhs, operator: Operator::LTE, rhs });
+        }
+        // a loose row over every variable keeps x7 from starting in the basis
+        let lhs = x.iter().fold(Expression::zero(), |lhs, &x| lhs + (1.0 * x));
+        constraints.push(Constraint { lhs, operator: Operator::LTE, rhs: Expression::zero() + 1e6 });
+        let objective = (0..7).fold(Expression::zero(), |objective, j| {
+            objective + (2.0f64.powi(6 - j as i32) * x[j])
+        });
+        let program = LinearProgram { objective: Objective::new(objective, true), constraints };
+
+        let tableau = simplex_method_solver(&program).unwrap();
+        let options = SolverOptions { simplex_method: SimplexMethod::Revised, ..SolverOptions::default() };
+        let revised = simplex_method_solver_with_options(&program, &options).unwrap();
+        assert_eq!(revised.status, SolveStatus::Optimal);
+        assert_eq!(revised.iterations, 127);
+        assert_eq!(revised.iterations, tableau.iterations);
+        assert_close(revised.objective_value, 78125.0);
+        assert_close(revised.values[&x[6]], 78125.0);
+    }
+
+    #[test]
+    fn test_dual_simplex_from_slack_basis() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        // the surplus variables make a dual feasible basis as every cost is positive
+        let program = LinearProgram {
+            objective: Objective::new((2.0 * x) + (3.0 * y), false),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (1.0 * x) + (1.0 * y), operator: Operator::GTE, rhs: Expression::zero() + 4.0 },
+                Constraint { lhs: (1.0 * x) + (3.0 * y), operator: Operator::GTE, rhs: Expression::zero() + 6.0 },
+            ],
+        };
+        for simplex_method in [SimplexMethod::Tableau, SimplexMethod::Revised] {
+            let options = SolverOptions {
+                algorithm: Algorithm::DualSimplex,
+                simplex_method,
+                record_trace: true,
+                ..SolverOptions::default()
+            };
+            let solution = simplex_method_solver_with_options(&program, &options).unwrap();
+            assert_eq!(solution.status, SolveStatus::Optimal);
+            assert_close(solution.objective_value, 9.0);
+            assert_close(solution.values[&x], 3.0);
+            assert_close(solution.values[&y], 1.0);
+            assert!(solution.trace.iter().all(|pivot| pivot.phase == Phase::Two));
+        }
+    }
+
+    #[test]
+    fn test_warm_start_after_adding_a_cut() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let mut program = LinearProgram {
+            objective: Objective::new((3.0 * x) + (5.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (1.0 * x) + (2.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 14.0 },
+                Constraint { lhs: (3.0 * x) + (-1.0 * y), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (1.0 * x) + (-1.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 2.0 },
+            ],
+        };
+        let solution = simplex_method_solver(&program).unwrap();
+        assert_close(solution.objective_value, 38.0);
+        let basis = solution.basis.unwrap();
+
+        // cut off the optimal vertex at (6, 4)
+        program.constraints.push(Constraint {
+            lhs: x.into(),
+            operator: Operator::LTE,
+            rhs: Expression::zero() + 5.0,
+        });
+        for simplex_method in [SimplexMethod::Tableau, SimplexMethod::Revised] {
+            let options = SolverOptions { simplex_method, ..SolverOptions::default() };
+            let cold = simplex_method_solver_with_options(&program, &options).unwrap();
+            let warm = simplex_method_solver_from_basis(&program, &options, &basis).unwrap();
+            assert_eq!(warm.status, SolveStatus::Optimal);
+            assert_close(warm.objective_value, cold.objective_value);
+            assert_close(warm.values[&x], 5.0);
+            assert_close(warm.values[&y], 4.5);
+            assert_eq!(warm.iterations, 1);
+        }
+    }
+
+    #[test]
+    fn test_bound_flips_and_free_variable() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let z = Variable::new("z");
+        // x and y reach their upper bounds without entering the basis and z
+        // keeps a single column despite having no bounds
+        let program = LinearProgram {
+            objective: Objective::new((1.0 * x) + (1.0 * y) + (1.0 * z), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: x.into(), operator: Operator::LTE, rhs: Expression::zero() + 2.0 },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (2.0 * y).into(), operator: Operator::LTE, rhs: Expression::zero() + 6.0 },
+                Constraint { lhs: (1.0 * x) + (1.0 * y) + (2.0 * z), operator: Operator::LTE, rhs: Expression::zero() + 10.0 },
+                Constraint { lhs: (1.0 * z) + (-1.0 * x), operator: Operator::LTE, rhs: Expression::zero() - 1.0 },
+            ],
+        };
+        for simplex_method in [SimplexMethod::Tableau, SimplexMethod::Revised] {
+            let options = SolverOptions { simplex_method, record_trace: true, ..SolverOptions::default() };
+            let solution = simplex_method_solver_with_options(&program, &options).unwrap();
+            assert_eq!(solution.status, SolveStatus::Optimal);
+            assert_close(solution.objective_value, 6.0);
+            assert_close(solution.values[&x], 2.0);
+            assert_close(solution.values[&y], 3.0);
+            assert_close(solution.values[&z], 1.0);
+            assert!(solution.trace.iter().any(|pivot| pivot.entering == pivot.leaving));
+        }
+    }
+
+    #[test]
+    fn test_shadow_prices_reduced_costs_and_slacks() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let program = LinearProgram {
+            objective: Objective::new((3.0 * x) + (5.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: x.into(), operator: Operator::LTE, rhs: Expression::zero() + 4.0 },
+                Constraint { lhs: (2.0 * y).into(), operator: Operator::LTE, rhs: Expression::zero() + 12.0 },
+                Constraint { lhs: (3.0 * x) + (2.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 18.0 },
+            ],
+        };
+        for simplex_method in [SimplexMethod::Tableau, SimplexMethod::Revised] {
+            let options = SolverOptions { simplex_method, ..SolverOptions::default() };
+            let solution = simplex_method_solver_with_options(&program, &options).unwrap();
+            // 2y <= 12 acts as the bound y <= 6, which passes on the reduced cost of y halved
+            for (actual, expected) in solution.shadow_prices.iter().zip([0.0, 0.0, 0.0, 1.5, 1.0]) {
+                assert_close(*actual, expected);
+            }
+            assert_close(solution.reduced_cost(&x).unwrap(), 0.0);
+            assert_close(solution.reduced_cost(&y).unwrap(), 3.0);
+            for (actual, expected) in solution.slacks.iter().zip([2.0, 6.0, 2.0, 0.0, 0.0]) {
+                assert_close(*actual, expected);
+            }
+        }
+
+        // minimising gives the rate of change of the minimum
+        let program = LinearProgram {
+            objective: Objective::new((2.0 * x) + (3.0 * y), false),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (1.0 * x) + (1.0 * y), operator: Operator::GTE, rhs: Expression::zero() + 4.0 },
+                Constraint { lhs: (1.0 * x) + (3.0 * y), operator: Operator::GTE, rhs: Expression::zero() + 6.0 },
+            ],
+        };
+        let solution = simplex_method_solver(&program).unwrap();
+        for (actual, expected) in solution.shadow_prices.iter().zip([0.0, 0.0, 1.5, 0.5]) {
+            assert_close(*actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_sensitivity_ranges() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let program = LinearProgram {
+            objective: Objective::new((3.0 * x) + (5.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: x.into(), operator: Operator::LTE, rhs: Expression::zero() + 4.0 },
+                Constraint { lhs: (2.0 * y).into(), operator: Operator::LTE, rhs: Expression::zero() + 12.0 },
+                Constraint { lhs: (3.0 * x) + (2.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 18.0 },
+            ],
+        };
+        for simplex_method in [SimplexMethod::Tableau, SimplexMethod::Revised] {
+            let options = SolverOptions { simplex_method, ..SolverOptions::default() };
+            let solution = simplex_method_solver_with_options(&program, &options).unwrap();
+            let report = solution.sensitivity.unwrap();
+            let x_range = report.objective_coefficients[&x];
+            assert_close(x_range.lower, 0.0);
+            assert_close(x_range.upper, 7.5);
+            let y_range = report.objective_coefficients[&y];
+            assert_close(y_range.lower, 2.0);
+            assert_eq!(y_range.upper, f64::INFINITY);
+
+            let expected = [
+                (f64::NEG_INFINITY, 2.0),
+                (f64::NEG_INFINITY, 6.0),
+                (2.0, f64::INFINITY),
+                (6.0, 18.0),
+                (12.0, 24.0),
+            ];
+            for (range, (lower, upper)) in report.right_hand_sides.iter().zip(expected) {
+                for (actual, expected) in [(range.lower, lower), (range.upper, upper)] {
+                    if expected.is_finite() {
+                        assert_close(actual, expected);
+                    } else {
+                        assert_eq!(actual, expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_infeasible() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let program = LinearProgram {
+            objective: Objective::new((1.0 * x) + (1.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (1.0 * x) + (1.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 2.0 },
+                Constraint { lhs: (1.0 * x) + (2.0 * y), operator: Operator::GTE, rhs: Expression::zero() + 5.0 },
+            ],
+        };
+        let solution = simplex_method_solver(&program).unwrap();
+        assert_eq!(solution.status, SolveStatus::Infeasible);
+    }
+
+    #[test]
+    fn test_farkas_certificate_and_irreducible_subset() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let program = LinearProgram {
+            objective: Objective::new((1.0 * x) + (1.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::LTE, rhs: Expression::zero() + 10.0 },
+                Constraint { lhs: (1.0 * x) + (1.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 2.0 },
+                Constraint { lhs: (1.0 * x) + (-1.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 3.0 },
+                Constraint { lhs: (1.0 * x) + (2.0 * y), operator: Operator::GTE, rhs: Expression::zero() + 5.0 },
+            ],
+        };
+        for algorithm in [Algorithm::PrimalSimplex, Algorithm::DualSimplex] {
+            let options = SolverOptions { algorithm, ..SolverOptions::default() };
+            let solution = simplex_method_solver_with_options(&program, &options).unwrap();
+            assert_eq!(solution.status, SolveStatus::Infeasible);
+
+            // the weighted sum of the constraints has no variables left and
+            // reads 0 <= bound with the bound negative
+            let mut sum = Expression::zero();
+            let mut bound = 0.0;
+            for (c, &multiplier) in program.constraints.iter().zip(&solution.farkas_certificate) {
+                match c.operator {
+                    Operator::LTE => assert!(multiplier >= 0.0),
+                    Operator::GTE => assert!(multiplier <= 0.0),
+                    Operator::Eq => {}
+                }
+                sum += &c.lhs * multiplier;
+                sum -= &c.rhs * multiplier;
+                bound += multiplier * (c.rhs.constant - c.lhs.constant);
+            }
+            assert!(sum.terms.iter().all(|term| term.coefficient.abs() < 1e-9));
+            assert!(bound < 0.0);
+
+            let subset = find_irreducible_infeasible_subset(&program, &options).unwrap();
+            assert_eq!(subset, Some(vec![0, 2, 4]));
+        }
+
+        let mut constraints = program.constraints;
+        constraints.pop();
+        let feasible = LinearProgram { objective: program.objective, constraints };
+        assert_eq!(find_irreducible_infeasible_subset(&feasible, &SolverOptions::default()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_constraint_without_variables() {
+        let x = Variable::new("x");
+        let program = LinearProgram {
+            objective: Objective::new(x.into(), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::LTE, rhs: Expression::zero() + 1.0 },
+                Constraint { lhs: (1.0 * x) + 1.0, operator: Operator::GTE, rhs: Expression::from(x) + 2.0 },
+            ],
+        };
+        let solution = simplex_method_solver(&program).unwrap();
+        assert_eq!(solution.status, SolveStatus::Infeasible);
+        assert!(solution.values.is_empty());
+        assert_eq!(solution.farkas_certificate, vec![0.0, -1.0]);
+    }
+
+    #[test]
+    fn test_terms_cancelling_up_to_round_off() {
+        let x = Variable::new("x");
+        // 0.1x + 0.2x - 0.3x leaves a coefficient of about 5.6e-17 behind,
+        // which read exactly would bound x above by zero
+        let program = LinearProgram {
+            objective: Objective::new(x.into(), false),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() + 1.0 },
+                Constraint { lhs: (0.1 * x) + (0.2 * x), operator: Operator::LTE, rhs: (0.3 * x).into() },
+            ],
+        };
+        let solution = simplex_method_solver(&program).unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_close(solution.values[&x], 1.0);
+
+        let exact = SolverOptions { zero_tolerance: 0.0, ..SolverOptions::default() };
+        let solution = simplex_method_solver_with_options(&program, &exact).unwrap();
+        assert_eq!(solution.status, SolveStatus::Infeasible);
+    }
+
+    #[test]
+    fn test_presolve() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let z = Variable::new("z");
+        let w = Variable::new("w");
+        let mut program = LinearProgram {
+            objective: Objective::new((2.0 * x) + (3.0 * y) + (1.0 * z) + (-1.0 * w), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: x.into(), operator: Operator::LTE, rhs: Expression::zero() + 3.0 },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::LTE, rhs: Expression::zero() + 3.0 },
+                // z is fixed, which leaves x + y <= 4 behind
+                Constraint { lhs: z.into(), operator: Operator::GTE, rhs: Expression::zero() + 2.0 },
+                Constraint { lhs: (2.0 * z).into(), operator: Operator::LTE, rhs: Expression::zero() + 4.0 },
+                Constraint { lhs: (1.0 * x) + (1.0 * y) + (1.0 * z), operator: Operator::LTE, rhs: Expression::zero() + 6.0 },
+                // a looser multiple of the row above, and a row the bounds always satisfy
+                Constraint { lhs: (2.0 * x) + (2.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 10.0 },
+                Constraint { lhs: (1.0 * x) + (-1.0 * y), operator: Operator::GTE, rhs: Expression::zero() - 10.0 },
+                // w is in no other constraint and its cost keeps it at its lower bound
+                Constraint { lhs: w.into(), operator: Operator::GTE, rhs: Expression::zero() + 1.0 },
+                Constraint { lhs: w.into(), operator: Operator::LTE, rhs: Expression::zero() + 5.0 },
+            ],
+        };
+        let options = SolverOptions { presolve: true, ..SolverOptions::default() };
+        let presolved = simplex_method_solver_with_options(&program, &options).unwrap();
+        let solution = simplex_method_solver(&program).unwrap();
+        assert_eq!(presolved.status, SolveStatus::Optimal);
+        assert_close(presolved.objective_value, 12.0);
+        assert_close(presolved.objective_value, solution.objective_value);
+        for var in [x, y, z, w] {
+            assert_close(presolved.values[&var], solution.values[&var]);
+        }
+        for (slack, expected) in presolved.slacks.iter().zip(&solution.slacks) {
+            assert_close(*slack, *expected);
+        }
+        assert!(presolved.sensitivity.is_none());
+        assert!(presolved.basis.unwrap().variables.len() < solution.basis.unwrap().variables.len());
+
+        // undoing the reductions gives every constraint and variable its
+        // multiplier back, z and w through the bounds they were fixed by
+        let expected = [0.0, 0.0, 0.0, 1.0, -1.0, 0.0, 2.0, 0.0, 0.0, -1.0, 0.0];
+        for (index, &price) in expected.iter().enumerate() {
+            assert_close(presolved.shadow_prices[index], price);
+            assert_close(solution.shadow_prices[index], price);
+        }
+        for (var, reduced_cost) in [(x, 0.0), (y, 1.0), (z, -1.0), (w, -1.0)] {
+            assert_close(presolved.reduced_costs[&var], reduced_cost);
+            assert_close(solution.reduced_costs[&var], reduced_cost);
+        }
+
+        // x + y >= 5 contradicts the row left behind by fixing z
+        program.constraints.push(Constraint {
+            lhs: (1.0 * x) + (1.0 * y),
+            operator: Operator::GTE,
+            rhs: Expression::zero() + 5.0,
+        });
+        let presolved = simplex_method_solver_with_options(&program, &options).unwrap();
+        assert_eq!(presolved.status, SolveStatus::Infeasible);
+        assert_eq!(presolved.farkas_certificate.len(), program.constraints.len());
+    }
+
+    #[test]
+    fn test_scaling_rows_of_very_different_sizes() {
+        let a = Variable::new("a");
+        let b = Variable::new("b");
+        let c = Variable::new("c");
+        // unscaled, round-off in the small rows leaves phase I short of zero
+        let program = LinearProgram {
+            objective: Objective::new((2.0 * a) + (-4.0 * b) + (2.0 * c), false),
+            constraints: vec![
+                Constraint { lhs: (-3e5 * a) + (-3e5 * b) + (-2e5 * c), operator: Operator::LTE, rhs: Expression::zero() + 8e5 },
+                Constraint { lhs: (-2e6 * a) + (1e6 * b) + (-3e6 * c), operator: Operator::LTE, rhs: Expression::zero() + 1e6 },
+                Constraint { lhs: (-20.0 * a) + (20.0 * b) + (-30.0 * c), operator: Operator::LTE, rhs: Expression::zero() + 30.0 },
+                Constraint { lhs: (-1e-4 * a) + (-1e-4 * b), operator: Operator::LTE, rhs: Expression::zero() },
+                Constraint { lhs: (2.0 * a).into(), operator: Operator::GTE, rhs: Expression::zero() - 2.0 },
+                Constraint { lhs: a.into(), operator: Operator::LTE, rhs: Expression::zero() },
+                Constraint { lhs: (2.0 * b).into(), operator: Operator::GTE, rhs: Expression::zero() - 8.0 },
+                Constraint { lhs: b.into(), operator: Operator::LTE, rhs: Expression::zero() + 1.0 },
+                Constraint { lhs: (2.0 * c).into(), operator: Operator::GTE, rhs: Expression::zero() + 4.0 },
+                Constraint { lhs: c.into(), operator: Operator::LTE, rhs: Expression::zero() + 6.0 },
+            ],
+        };
+        let options = SolverOptions { scaling: true, ..SolverOptions::default() };
+        let solution = simplex_method_solver_with_options(&program, &options).unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_close(solution.objective_value, -2.0);
+
+        // the duals and ranges come back in the terms of the program as
+        // given, where the shadow prices make up every cost
+        for (var, cost) in [(a, 2.0), (b, -4.0), (c, 2.0)] {
+            let total = program
+                .constraints
+                .iter()
+                .zip(&solution.shadow_prices)
+                .flat_map(|(c, price)| c.lhs.terms.iter().filter(|term| term.variable == var).map(move |term| price * term.coefficient))
+                .sum::<f64>();
+            assert_close(total, cost);
+        }
+        let report = solution.sensitivity.unwrap();
+        assert!(report.right_hand_sides[9].contains(6.0));
+        assert!(report.objective_coefficients[&b].contains(-4.0));
+    }
+
+    #[test]
+    fn test_iteration_and_time_limits() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let program = LinearProgram {
+            objective: Objective::new((3.0 * x) + (5.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: x.into(), operator: Operator::LTE, rhs: Expression::zero() + 4.0 },
+                Constraint { lhs: (3.0 * x) + (2.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 18.0 },
+                Constraint { lhs: (1.0 * x) + (2.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 14.0 },
+            ],
+        };
+        for algorithm in [Algorithm::PrimalSimplex, Algorithm::DualSimplex, Algorithm::InteriorPoint] {
+            let options = SolverOptions { algorithm, iteration_limit: 1, ..SolverOptions::default() };
+            let solution = simplex_method_solver_with_options(&program, &options).unwrap();
+            assert_eq!(solution.status, SolveStatus::IterationLimit);
+            assert_eq!(solution.iterations, 1);
+            assert!(solution.shadow_prices.is_empty());
+        }
+
+        for algorithm in [Algorithm::PrimalSimplex, Algorithm::DualSimplex, Algorithm::InteriorPoint] {
+            let options = SolverOptions {
+                algorithm,
+                time_limit: Some(std::time::Duration::ZERO),
+                ..SolverOptions::default()
+            };
+            let solution = simplex_method_solver_with_options(&program, &options).unwrap();
+            assert_eq!(solution.status, SolveStatus::TimeLimit);
+            assert_eq!(solution.iterations, 0);
+        }
+
+        // a basis that is already optimal needs no pivots to finish
+        let options = SolverOptions { iteration_limit: 0, ..SolverOptions::default() };
+        let minimize = LinearProgram { objective: Objective::new((3.0 * x) + (5.0 * y), false), ..program };
+        let solution = simplex_method_solver_with_options(&minimize, &options).unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_close(solution.objective_value, 0.0);
+    }
+
+    #[test]
+    fn test_non_finite_coefficient() {
+        let x = Variable::new("x");
+        let program = LinearProgram {
+            objective: Objective::new(x.into(), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::LTE, rhs: Expression::zero() + 1.0 },
+                Constraint { lhs: (f64::NAN * x).into(), operator: Operator::LTE, rhs: Expression::zero() },
+            ],
+        };
+        let error = simplex_method_solver(&program).unwrap_err();
+        assert_eq!(error, SolveError::NonFiniteConstraint { index: 1 });
+    }
+
+    #[test]
+    fn test_branch_and_bound() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        // the relaxation is optimal at x = 3, y = 1.5 with 21
+        let mut mip = MixedIntegerProgram::new(LinearProgram {
+            objective: Objective::new((5.0 * x) + (4.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (6.0 * x) + (4.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 24.0 },
+                Constraint { lhs: (1.0 * x) + (2.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 6.0 },
+            ],
+        });
+        mip.set_integer(x);
+        mip.set_integer(y);
+        // cutting planes alone close the gap, so the root is the only node
+        let options = SolverOptions { cut_rounds: 50, ..SolverOptions::default() };
+        let solution = cutting_plane_solver_with_options(&mip, &options).unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_close(solution.objective_value, 20.0);
+        assert_eq!(solution.nodes, 1);
+        for node_selection in [NodeSelection::BestBound, NodeSelection::DepthFirst] {
+            let options = SolverOptions { node_selection, cut_rounds: 0, ..SolverOptions::default() };
+            let solution = branch_and_bound_solver_with_options(&mip, &options).unwrap();
+            assert_eq!(solution.status, SolveStatus::Optimal);
+            assert_close(solution.objective_value, 20.0);
+            assert_close(solution.best_bound, 20.0);
+            assert_close(solution.values[&x], 4.0);
+            assert_close(solution.values[&y], 0.0);
+            assert!(solution.nodes > 1);
+        }
+    }
+
+    #[test]
+    fn test_branch_and_bound_stopping_criteria() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let mut mip = MixedIntegerProgram::new(LinearProgram {
+            objective: Objective::new((5.0 * x) + (4.0 * y), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: y.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (6.0 * x) + (4.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 24.0 },
+                Constraint { lhs: (1.0 * x) + (2.0 * y), operator: Operator::LTE, rhs: Expression::zero() + 6.0 },
+            ],
+        });
+        mip.set_integer(x);
+        mip.set_integer(y);
+        // branching alone, so the search takes a few nodes
+        let options = SolverOptions {
+            cut_rounds: 0,
+            rounding: false,
+            diving: false,
+            feasibility_pump: false,
+            ..SolverOptions::default()
+        };
+
+        let solution =
+            branch_and_bound_solver_with_options(&mip, &SolverOptions { record_trace: true, ..options.clone() }).unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.gap, 0.0);
+        let last = solution.progress.last().unwrap();
+        assert_eq!((last.objective_value, last.best_bound, last.gap), (20.0, 20.0, 0.0));
+        for pair in solution.progress.windows(2) {
+            assert!(pair[1].best_bound <= pair[0].best_bound);
+            assert!(pair[1].nodes >= pair[0].nodes);
+        }
+
+        // the root relaxation is fractional with a bound of 21
+        let solution =
+            branch_and_bound_solver_with_options(&mip, &SolverOptions { node_limit: Some(1), ..options.clone() })
+                .unwrap();
+        assert_eq!(solution.status, SolveStatus::NodeLimit);
+        assert_eq!(solution.nodes, 1);
+        assert_close(solution.best_bound, 21.0);
+        assert!(solution.objective_value.is_nan());
+        assert_eq!(solution.gap, f64::INFINITY);
+
+        // any integer solution is within a gap of 100%
+        let solution =
+            branch_and_bound_solver_with_options(&mip, &SolverOptions { relative_gap: 1.0, ..options.clone() })
+                .unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert!(solution.gap <= 1.0);
+        assert!(solution.objective_value <= solution.best_bound);
+
+        // rounding the root relaxation x = 1/3 down gives an incumbent of zero
+        let mut zero_mip = MixedIntegerProgram::new(LinearProgram {
+            objective: Objective::new(x.into(), true),
+            constraints: vec![
+                Constraint { lhs: x.into(), operator: Operator::GTE, rhs: Expression::zero() },
+                Constraint { lhs: (3.0 * x).into(), operator: Operator::LTE, rhs: Expression::zero() + 1.0 },
+            ],
+        });
+        zero_mip.set_integer(x);
+        let gap_options = SolverOptions { rounding: true, relative_gap: 0.5, ..options.clone() };
+        let solution = branch_and_bound_solver_with_options(&zero_mip, &gap_options).unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.nodes, 1);
+        assert_eq!(solution.objective_value, 0.0);
+        assert_close(solution.best_bound, 1.0 / 3.0);
+        assert_close(solution.gap, 1.0 / 3.0);
+
+        // no time is left for the root
+        let options = SolverOptions { time_limit: Some(std::time::Duration::ZERO), ..options };
+        let solution = branch_and_bound_solver_with_options(&mip, &options).unwrap();
+        assert_eq!(solution.status, SolveStatus::TimeLimit);
+        assert_eq!(solution.nodes, 0);
+    }
+
+    #[test]
+    fn test_branch_and_bound_with_binary_variables() {
+        let a = Variable::new("a");
+        let b = Variable::new("b");
+        let c = Variable::new("c");
+        let mut mip = MixedIntegerProgram::new(LinearProgram {
+            objective: Objective::new((-10.0 * a) + (-13.0 * b) + (-7.0 * c), false),
+            constraints: vec![Constraint {
+                lhs: (3.0 * a) + (4.0 * b) + (2.0 * c),
+                operator: Operator::LTE,
+                rhs: Expression::zero() + 6.0,
+            }],
+        });
+        for variable in [a, b, c] {
+            mip.set_binary(variable);
+        }
+        let solution = branch_and_bound_solver(&mip).unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_close(solution.objective_value, -20.0);
+        assert_close(solution.values[&a], 0.0);
+        assert_close(solution.values[&b], 1.0);
+        assert_close(solution.values[&c], 1.0);
+        assert_close(mip.program.objective.expression.evaluate(&solution).unwrap(), -20.0);
+
+        // no integer lies between the bounds the relaxation leaves
+        let x = Variable::new("x");
+        let mut mip = MixedIntegerProgram::new(LinearProgram {
+            objective: Objective::new(x.into(), true),
+            constraints: vec![Constraint { lhs: (2.0 * x).into(), operator: Operator::Eq, rhs: Expression::zero() + 1.0 }],
+        });
+        mip.set_integer(x);
+        let solution = branch_and_bound_solver(&mip).unwrap();
+        assert_eq!(solution.status, SolveStatus::Infeasible);
+        assert!(solution.objective_value.is_nan());
+        assert_eq!(solution.nodes, 3);
+    }
+}
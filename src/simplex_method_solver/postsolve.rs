@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use crate::Variable;
+
+// A reduction presolving made to the program, holding what is needed to
+// undo it on the dual side. The value of a fixed variable comes back through
+// the substitution made for it
+pub enum Reduction<'a> {
+    // a variable fixed at a value, along with its cost, the constraints of
+    // the rows it was in when it was fixed with its coefficient in each, and
+    // the constraints its lower and upper bounds came from with its
+    // coefficient in them
+    FixedVariable {
+        variable: Variable<'a>,
+        cost: f64,
+        rows: Vec<(usize, f64)>,
+        lower_origin: Option<(usize, f64)>,
+        upper_origin: Option<(usize, f64)>,
+    },
+    // a constraint dropped as a duplicate of another or for always holding,
+    // which leaves the optimum where it is
+    RemovedConstraint { origin: usize },
+}
+
+// The reductions in the order they were made, undone in the reverse order.
+// Every reduction only touches constraints that are still rows when it is
+// made, so the multipliers of those rows are known by the time it is undone
+#[derive(Default)]
+pub struct Postsolve<'a> {
+    reductions: Vec<Reduction<'a>>,
+}
+
+impl<'a> Postsolve<'a> {
+    pub fn push(&mut self, reduction: Reduction<'a>) {
+        self.reductions.push(reduction);
+    }
+
+    // Completes the multiplier of every constraint from those found for the
+    // rows and bounds that were solved, in the terms of the standard form,
+    // giving the reduced cost of every variable that was fixed. A fixed
+    // variable's reduced cost is what is left of its cost once the rows it
+    // was in have taken their share, and passes on to the constraint that
+    // gave the bound holding it, as it does for a column
+    pub fn restore_multipliers(&self, multipliers: &mut [f64], tolerance: f64) -> HashMap<Variable<'a>, f64> {
+        let mut reduced_costs = HashMap::new();
+        for reduction in self.reductions.iter().rev() {
+            match reduction {
+                Reduction::FixedVariable { variable, cost, rows, lower_origin, upper_origin } => {
+                    let reduced_cost = cost
+                        - rows
+                            .iter()
+                            .map(|&(origin, coefficient)| multipliers[origin] * coefficient)
+                            .sum::<f64>();
+                    let origin = if reduced_cost > tolerance {
+                        *upper_origin
+                    } else if reduced_cost < -tolerance {
+                        *lower_origin
+                    } else {
+                        None
+                    };
+                    if let Some((constraint, coefficient)) = origin {
+                        multipliers[constraint] = reduced_cost / coefficient;
+                    }
+                    reduced_costs.insert(*variable, reduced_cost);
+                }
+                Reduction::RemovedConstraint { origin } => multipliers[*origin] = 0.0,
+            }
+        }
+        reduced_costs
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::{Constraint, Expression, Operator, Substitutable, Variable};
+
+use super::{
+    postsolve::Reduction,
+    program_transformer::ProgramTransformer,
+};
+
+// Reduces the program before it is solved, once its constraints on single
+// variables have been turned into bounds. Every reduction can make others
+// possible, as a variable fixed in place can leave a constraint on a single
+// variable behind, which gives a bound that can make another constraint
+// redundant, so they are repeated until none of them removes anything
+pub fn presolve(tf: &mut ProgramTransformer) {
+    loop {
+        let size = (tf.constraints.len(), tf.substitutions.len());
+        remove_fixed_variables(tf);
+        tf.make_single_variable_constraints_bounds();
+        tf.eliminate_empty_constraints();
+        if tf.infeasible {
+            return;
+        }
+        remove_duplicate_constraints(tf);
+        remove_redundant_constraints(tf);
+        fix_empty_columns(tf);
+        if tf.infeasible || (tf.constraints.len(), tf.substitutions.len()) == size {
+            return;
+        }
+    }
+}
+
+// Substitutes the value of every variable whose bounds leave it no room to
+// move into the constraints and the objective
+fn remove_fixed_variables(tf: &mut ProgramTransformer) {
+    let fixed = tf
+        .bounds
+        .iter()
+        .filter(|(_, (lower, upper))| lower.is_finite() && upper - lower <= tf.primal_tolerance)
+        .map(|(variable, (lower, _))| (*variable, *lower))
+        .collect::<Vec<_>>();
+    for (variable, value) in fixed {
+        fix(tf, variable, value);
+    }
+    // the values substituted end up on the left hand sides
+    tf.make_constraints_single_sided();
+}
+
+// Substitutes the value of the variable, recording the rows and the bounds
+// it leaves behind
+fn fix<'a>(tf: &mut ProgramTransformer<'a>, variable: Variable<'a>, value: f64) {
+    let coefficient = |expr: &Expression<'a>| {
+        expr.terms.iter().find(|term| term.variable == variable).map(|term| term.coefficient)
+    };
+    let rows = tf
+        .constraints
+        .iter()
+        .zip(&tf.origins)
+        .filter_map(|(c, &origin)| coefficient(&c.lhs).map(|coefficient| (origin, coefficient)))
+        .collect();
+    tf.postsolve.push(Reduction::FixedVariable {
+        variable,
+        cost: coefficient(&tf.objective.expression).unwrap_or(0.0),
+        rows,
+        lower_origin: tf.lower_origins.get(&variable).copied(),
+        upper_origin: tf.upper_origins.get(&variable).copied(),
+    });
+    tf.bounds.remove(&variable);
+    tf.substitute(&variable, &(Expression::zero() + value));
+}
+
+// Drops the constraints at the indices given
+fn remove_constraints(tf: &mut ProgramTransformer, indices: &HashSet<usize>) {
+    let mut removed = vec![];
+    let mut index = 0;
+    tf.retain_constraints(|_, origin| {
+        index += 1;
+        let keep = !indices.contains(&(index - 1));
+        if !keep {
+            removed.push(origin);
+        }
+        keep
+    });
+    for origin in removed {
+        tf.postsolve.push(Reduction::RemovedConstraint { origin });
+    }
+}
+
+// The interval of values the left hand side of a constraint is held to, in
+// units of another left hand side it is the given multiple of
+fn interval(c: &Constraint, multiple: f64) -> (f64, f64) {
+    let value = c.rhs.constant / multiple;
+    match (c.operator, multiple > 0.0) {
+        (Operator::Eq, _) => (value, value),
+        (Operator::LTE, true) | (Operator::GTE, false) => (f64::NEG_INFINITY, value),
+        (Operator::LTE, false) | (Operator::GTE, true) => (value, f64::INFINITY),
+    }
+}
+
+// The multiple one left hand side is of another with the same variables, if
+// it is one
+fn multiple_of(c: &Constraint, other: &Constraint, tolerance: f64) -> Option<f64> {
+    let coefficients = other
+        .lhs
+        .terms
+        .iter()
+        .map(|term| (term.variable, term.coefficient))
+        .collect::<HashMap<_, _>>();
+    let first = &c.lhs.terms[0];
+    let multiple = first.coefficient / coefficients[&first.variable];
+    c.lhs
+        .terms
+        .iter()
+        .all(|term| {
+            let expected = multiple * coefficients[&term.variable];
+            (term.coefficient - expected).abs() <= tolerance * term.coefficient.abs().max(1.0)
+        })
+        .then_some(multiple)
+}
+
+// Keeps only the tightest of the constraints whose left hand sides are
+// multiples of one another, one giving the lower end of the interval they
+// hold that left hand side to and one giving the upper end, which are the
+// same constraint when it is an equality. The program is infeasible if the
+// interval is empty
+fn remove_duplicate_constraints(tf: &mut ProgramTransformer) {
+    let mut groups: HashMap<Vec<Variable>, Vec<usize>> = HashMap::new();
+    for (index, c) in tf.constraints.iter().enumerate() {
+        let variables = c.lhs.terms.iter().map(|term| term.variable).sorted().collect();
+        groups.entry(variables).or_default().push(index);
+    }
+
+    let mut removed = HashSet::new();
+    for indices in groups.into_values().filter(|indices| indices.len() > 1) {
+        // each constraint is measured against the first of its multiples
+        let mut classes: Vec<Vec<(usize, f64)>> = vec![];
+        for index in indices {
+            let c = &tf.constraints[index];
+            let class = classes.iter_mut().find_map(|class| {
+                multiple_of(c, &tf.constraints[class[0].0], tf.zero_tolerance).map(|multiple| (class, multiple))
+            });
+            match class {
+                Some((class, multiple)) => class.push((index, multiple)),
+                None => classes.push(vec![(index, 1.0)]),
+            }
+        }
+
+        for class in classes.into_iter().filter(|class| class.len() > 1) {
+            let (mut lower, mut upper) = ((f64::NEG_INFINITY, None), (f64::INFINITY, None));
+            for &(index, multiple) in &class {
+                let (low, high) = interval(&tf.constraints[index], multiple);
+                if low > lower.0 {
+                    lower = (low, Some(index));
+                }
+                if high < upper.0 {
+                    upper = (high, Some(index));
+                }
+            }
+            if lower.0 - upper.0 > tf.primal_tolerance {
+                tf.infeasible = true;
+                return;
+            }
+            removed.extend(
+                class
+                    .iter()
+                    .map(|&(index, _)| index)
+                    .filter(|&index| Some(index) != lower.1 && Some(index) != upper.1),
+            );
+        }
+    }
+
+    remove_constraints(tf, &removed);
+}
+
+// Removes the inequalities that hold whatever values the variables take
+// within their bounds
+fn remove_redundant_constraints(tf: &mut ProgramTransformer) {
+    let bounds = &tf.bounds;
+    let tolerance = tf.primal_tolerance;
+    let mut redundant = HashSet::new();
+    for (index, c) in tf.constraints.iter().enumerate() {
+        // the smallest and largest values the left hand side can take
+        let (smallest, largest) = c.lhs.terms.iter().fold((0.0, 0.0), |(smallest, largest), term| {
+            let (lower, upper) = bounds
+                .get(&term.variable)
+                .map_or((f64::NEG_INFINITY, f64::INFINITY), |&(lower, upper)| (lower, upper));
+            let (low, high) = if term.coefficient > 0.0 {
+                (term.coefficient * lower, term.coefficient * upper)
+            } else {
+                (term.coefficient * upper, term.coefficient * lower)
+            };
+            (smallest + low, largest + high)
+        });
+        let holds = match c.operator {
+            Operator::LTE => largest <= c.rhs.constant + tolerance,
+            Operator::GTE => smallest >= c.rhs.constant - tolerance,
+            Operator::Eq => false,
+        };
+        if holds {
+            redundant.insert(index);
+        }
+    }
+
+    remove_constraints(tf, &redundant);
+}
+
+// Fixes every variable that is in no constraint at the bound its cost
+// favours, or where the simplex method would leave it when it has no cost.
+// A variable whose cost favours a missing bound is left for the simplex
+// method to find the program unbounded along
+fn fix_empty_columns(tf: &mut ProgramTransformer) {
+    let in_constraints = tf
+        .constraints
+        .iter()
+        .flat_map(|c| c.lhs.terms.iter().map(|term| term.variable))
+        .collect::<HashSet<_>>();
+    let costs = tf
+        .objective
+        .expression
+        .terms
+        .iter()
+        .map(|term| (term.variable, term.coefficient))
+        .collect::<HashMap<_, _>>();
+    let empty = tf
+        .bounds
+        .keys()
+        .chain(costs.keys())
+        .filter(|variable| !in_constraints.contains(variable))
+        .copied()
+        .sorted()
+        .dedup()
+        .collect::<Vec<_>>();
+
+    for variable in empty {
+        let (lower, upper) = tf.bounds.get(&variable).copied().unwrap_or((f64::NEG_INFINITY, f64::INFINITY));
+        // the objective is maximised by now
+        let cost = costs.get(&variable).copied().unwrap_or(0.0);
+        let value = if cost > 0.0 {
+            upper
+        } else if cost < 0.0 || lower.is_finite() {
+            lower
+        } else if upper.is_finite() {
+            upper
+        } else {
+            0.0
+        };
+        if value.is_finite() {
+            fix(tf, variable, value);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::PivotRule;
+
+// Devex weights are reset to the current set of variables once they grow past
+// this, as the estimates drift further from the true edge lengths over time
+const DEVEX_RESET_THRESHOLD: f64 = 1e6;
+
+// Chooses the entering column for a pivot rule, keeping the weights the
+// steepest edge and devex rules divide the reduced costs by
+pub struct Pricing {
+    rule: PivotRule,
+    weights: Vec<f64>,
+}
+
+impl Pricing {
+    pub fn new(rule: PivotRule, columns: usize) -> Pricing {
+        Pricing {
+            rule,
+            weights: vec![1.0; columns],
+        }
+    }
+
+    pub fn rule(&self) -> PivotRule {
+        self.rule
+    }
+
+    pub fn set_weight(&mut self, column: usize, weight: f64) {
+        self.weights[column] = weight;
+    }
+
+    // Picks from the columns that can improve the objective, given as pairs of
+    // the column and the rate at which it improves the objective
+    pub fn choose_entering<I>(&self, candidates: I) -> Option<usize>
+    where
+        I: Iterator<Item = (usize, f64)>,
+    {
+        match self.rule {
+            PivotRule::Bland => candidates.map(|(column, _)| column).min(),
+            PivotRule::Dantzig => candidates
+                .max_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+                .map(|(column, _)| column),
+            PivotRule::SteepestEdge | PivotRule::Devex => candidates
+                .map(|(column, rate)| (column, rate * rate / self.weights[column]))
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(column, _)| column),
+        }
+    }
+
+    // Updates the steepest edge weights after a pivot by the recurrence of
+    // Goldfarb and Reid. Each nonbasic column comes with its entry in the pivot
+    // row divided by the pivot element and its inner product with the entering
+    // column, both in terms of the basis before the pivot
+    pub fn update_steepest_edge<I>(
+        &mut self,
+        entering: usize,
+        leaving: usize,
+        pivot: f64,
+        entering_weight: f64,
+        columns: I,
+    ) where
+        I: Iterator<Item = (usize, f64, f64)>,
+    {
+        for (column, ratio, product) in columns {
+            if column != entering && ratio != 0.0 {
+                let weight = self.weights[column] - 2.0 * ratio * product
+                    + ratio * ratio * entering_weight;
+                self.weights[column] = weight.max(1.0 + ratio * ratio);
+            }
+        }
+        self.weights[leaving] = f64::max(entering_weight / (pivot * pivot), 1.0);
+    }
+
+    // Updates the devex weights after a pivot. The ratios are the entries of
+    // the pivot row divided by the pivot element for each nonbasic column
+    pub fn update_devex<I>(&mut self, entering: usize, leaving: usize, ratios: I)
+    where
+        I: Iterator<Item = (usize, f64)>,
+    {
+        if self.rule != PivotRule::Devex {
+            return;
+        }
+        let entering_weight = self.weights[entering];
+        let mut leaving_weight = 1.0;
+        for (column, ratio) in ratios {
+            if column == leaving {
+                leaving_weight = f64::max(entering_weight * ratio * ratio, 1.0);
+            } else if column != entering {
+                self.weights[column] = self.weights[column].max(ratio * ratio * entering_weight);
+            }
+        }
+        self.weights[leaving] = leaving_weight;
+
+        if self.weights.iter().any(|&weight| weight > DEVEX_RESET_THRESHOLD) {
+            self.weights.fill(1.0);
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::{
+    lp_structs::VariableContainer,
+    Constraint, Expression, LinearProgram, Objective, Operator, SolverOptions, Substitutable, Term,
+    Variable,
+};
+
+use super::{
+    postsolve::Postsolve,
+    presolve::presolve,
+};
+
+// A constraint of the program on a single variable, which was turned into
+// the bound given on that variable
+#[derive(Clone, Copy, Debug)]
+pub struct BoundConstraint<'a> {
+    pub origin: usize,
+    pub variable: Variable<'a>,
+    pub operator: Operator,
+    pub coefficient: f64,
+    pub value: f64,
+}
+
+pub struct ProgramTransformer<'a> {
+    pub constraints: Vec<Constraint<'a>>,
+    // the index in the program of the constraint each row came from
+    pub origins: Vec<usize>,
+    pub objective: Objective<'a>,
+    pub objective_flipped: bool,
+    pub substitutions: HashMap<Variable<'a>, Expression<'a>>,
+    pub bounds: HashMap<Variable<'a>, (f64, f64)>,
+    // the constraint in the program each lower and upper bound came from,
+    // along with the coefficient of the variable in it
+    pub lower_origins: HashMap<Variable<'a>, (usize, f64)>,
+    pub upper_origins: HashMap<Variable<'a>, (usize, f64)>,
+    pub bound_constraints: Vec<BoundConstraint<'a>>,
+    pub next_temp_id: usize,
+    pub infeasible: bool,
+    // when the program is found to be infeasible before it is solved, the
+    // multiplier of each constraint in the program that proves it
+    pub farkas: Vec<(usize, f64)>,
+    // how far a constraint without variables may be violated and bounds may
+    // cross, and the size below which a coefficient is taken to be zero
+    pub primal_tolerance: f64,
+    pub zero_tolerance: f64,
+    // whether to presolve the program, and whether that removed anything
+    // from it or found it infeasible
+    presolve: bool,
+    pub presolved: bool,
+    // the reductions presolving made, to be undone on the solution
+    pub postsolve: Postsolve<'a>,
+}
+
+impl<'a> Substitutable<'a> for ProgramTransformer<'a> {
+    fn substitute(&mut self, var: &crate::Variable<'a>, expr: &Expression<'a>) {
+        for c in &mut self.constraints {
+            substitute_with_constant(&mut c.lhs, var, expr);
+            substitute_with_constant(&mut c.rhs, var, expr);
+        }
+        substitute_with_constant(&mut self.objective.expression, var, expr);
+        self.substitutions.insert(*var, expr.clone());
+    }
+}
+
+// Expression::substitute only rewrites the terms, so the constant part of the
+// replacement has to be carried across separately
+fn substitute_with_constant<'a>(
+    target: &mut Expression<'a>,
+    var: &Variable<'a>,
+    expr: &Expression<'a>,
+) {
+    let coefficient: f64 = target
+        .terms
+        .iter()
+        .filter(|term| &term.variable == var)
+        .map(|term| term.coefficient)
+        .sum();
+    target.substitute(var, expr);
+    target.constant += coefficient * expr.constant;
+}
+
+impl<'a> VariableContainer<'a> for ProgramTransformer<'a> {
+    type I = std::vec::IntoIter<Variable<'a>>;
+
+    fn variables_iter(&self) -> Self::I {
+        let variables = self.objective.variables_iter()
+            .chain(self.constraints.iter().flat_map(|c| c.variables_iter()))
+            .unique()
+            .collect::<Vec<_>>();
+        variables.into_iter()
+    }
+}
+
+// Returns the variable of a constraint in the form of nx <= y, nx >= y or
+// nx = y, along with the bound it places on that variable
+fn bound<'a>(c: &Constraint<'a>) -> Option<(Variable<'a>, Operator, f64)> {
+    if c.lhs.terms.len() == 1 && c.rhs.terms.is_empty() {
+        let Term {
+            coefficient,
+            variable,
+        } = c.lhs.terms[0];
+        let operator = if coefficient < 0.0 { c.operator.flip() } else { c.operator };
+        return Some((variable, operator, (c.rhs.constant - c.lhs.constant) / coefficient));
+    }
+    None
+}
+
+impl<'a> ProgramTransformer<'a> {
+    pub fn new<'b>(program: &LinearProgram<'b>, options: &SolverOptions) -> ProgramTransformer<'b> {
+        ProgramTransformer {
+            constraints: program.constraints.clone(),
+            origins: (0..program.constraints.len()).collect(),
+            objective: program.objective.clone(),
+            objective_flipped: false,
+            substitutions: HashMap::new(),
+            bounds: HashMap::new(),
+            lower_origins: HashMap::new(),
+            upper_origins: HashMap::new(),
+            bound_constraints: vec![],
+            next_temp_id: program.next_temp_id(),
+            infeasible: false,
+            farkas: vec![],
+            primal_tolerance: options.primal_feasibility_tolerance,
+            zero_tolerance: options.zero_tolerance,
+            presolve: options.presolve,
+            presolved: false,
+            postsolve: Postsolve::default(),
+        }
+    }
+
+    // Creates a temp variable that has not been used anywhere in the program,
+    // including the variables that have already been substituted away
+    pub fn new_temp_variable(&mut self) -> Variable<'a> {
+        let variable = Variable::Temp { id: self.next_temp_id };
+        self.next_temp_id += 1;
+        variable
+    }
+
+    pub fn make_constraints_single_sided(&mut self) {
+        self.constraints.iter_mut().for_each(|c| {
+            c.lhs -= Expression::from(c.rhs.terms.clone());
+            c.rhs -= c.lhs.constant;
+            c.lhs.constant = 0.0;
+            c.rhs.terms = vec![];
+        })
+    }
+
+    // Drops the terms whose coefficients are too small to be told apart from
+    // zero, such as those left by terms that cancel out up to round-off
+    fn drop_negligible_terms(&mut self) {
+        let zero_tolerance = self.zero_tolerance;
+        let expressions = self
+            .constraints
+            .iter_mut()
+            .map(|c| &mut c.lhs)
+            .chain([&mut self.objective.expression]);
+        for expr in expressions {
+            expr.terms.retain(|term| term.coefficient.abs() > zero_tolerance);
+        }
+    }
+
+    fn make_objective_maximizing(&mut self) {
+        if !self.objective.maximize {
+            self.objective.expression *= -1.0;
+            self.objective.maximize = true;
+            self.objective_flipped = true;
+        }
+    }
+
+    // Keeps the constraints for which the closure returns true, given each
+    // along with the index of the constraint in the program it came from
+    pub fn retain_constraints(&mut self, mut keep: impl FnMut(&Constraint<'a>, usize) -> bool) {
+        let (constraints, origins) = self
+            .constraints
+            .drain(..)
+            .zip(self.origins.drain(..))
+            .filter(|(c, origin)| keep(c, *origin))
+            .unzip();
+        self.constraints = constraints;
+        self.origins = origins;
+    }
+
+    // Turns every constraint on a single variable into a bound on that
+    // variable, keeping the tightest lower and upper bound of each along with
+    // those found before. Variables without a bound in either direction are
+    // free
+    pub fn make_single_variable_constraints_bounds(&mut self) {
+        let mut bounds = std::mem::take(&mut self.bounds);
+        let mut lower_origins = std::mem::take(&mut self.lower_origins);
+        let mut upper_origins = std::mem::take(&mut self.upper_origins);
+        let mut bound_constraints = std::mem::take(&mut self.bound_constraints);
+        self.retain_constraints(|c, origin| {
+            let Some((variable, operator, value)) = bound(c) else {
+                return true;
+            };
+            let coefficient = c.lhs.terms[0].coefficient;
+            bound_constraints.push(BoundConstraint { origin, variable, operator, coefficient, value });
+            let (lower, upper) = bounds.entry(variable).or_insert((f64::NEG_INFINITY, f64::INFINITY));
+            if operator != Operator::LTE && value > *lower {
+                *lower = value;
+                lower_origins.insert(variable, (origin, coefficient));
+            }
+            if operator != Operator::GTE && value < *upper {
+                *upper = value;
+                upper_origins.insert(variable, (origin, coefficient));
+            }
+            false
+        });
+        // a lower bound above an upper bound, ax >= b and cx <= d with
+        // b/a > d/c, is contradicted by adding -1/a times the first to 1/c
+        // times the second
+        let crossed = bounds
+            .iter()
+            .find(|(_, (lower, upper))| lower - upper > self.primal_tolerance);
+        if let Some((variable, _)) = crossed.filter(|_| !self.infeasible) {
+            let (lower_origin, lower_coefficient) = lower_origins[variable];
+            let (upper_origin, upper_coefficient) = upper_origins[variable];
+            self.farkas = vec![(lower_origin, -1.0 / lower_coefficient), (upper_origin, 1.0 / upper_coefficient)];
+            self.infeasible = true;
+        }
+        // bounds crossing by no more than the tolerance fix the variable at
+        // its upper bound, so no one downstream sees them crossed
+        for (lower, upper) in bounds.values_mut() {
+            if lower > upper {
+                *lower = *upper;
+            }
+        }
+        self.bounds = bounds;
+        self.lower_origins = lower_origins;
+        self.upper_origins = upper_origins;
+        self.bound_constraints = bound_constraints;
+    }
+
+    // Removes the constraints that no longer contain any variables, marking
+    // the program as infeasible if any of them can never be satisfied
+    pub fn eliminate_empty_constraints(&mut self) {
+        let mut violated = None;
+        let tolerance = self.primal_tolerance;
+        self.retain_constraints(|c, origin| {
+            if !c.lhs.terms.is_empty() {
+                return true;
+            }
+            let satisfied = match c.operator {
+                Operator::LTE => c.rhs.constant >= -tolerance,
+                Operator::Eq => c.rhs.constant.abs() <= tolerance,
+                Operator::GTE => c.rhs.constant <= tolerance,
+            };
+            if !satisfied && violated.is_none() {
+                violated = Some((origin, c.operator, c.rhs.constant));
+            }
+            false
+        });
+        // the constraint alone reads 0 <= b with b negative, once a >= or an
+        // equality with b positive is turned around
+        if let Some((origin, operator, constant)) = violated {
+            let multiplier = match operator {
+                Operator::LTE => 1.0,
+                Operator::GTE => -1.0,
+                Operator::Eq => -constant.signum(),
+            };
+            if self.farkas.is_empty() {
+                self.farkas = vec![(origin, multiplier)];
+            }
+            self.infeasible = true;
+        }
+    }
+
+    // Adds a slack variable to every inequality, so that ax <= b becomes
+    // ax + s = b and ax >= b becomes ax - s = b with s bounded below by zero
+    fn make_all_constraints_equality_constraints(&mut self) {
+        for index in 0..self.constraints.len() {
+            let coefficient = match self.constraints[index].operator {
+                Operator::LTE => 1.0,
+                Operator::GTE => -1.0,
+                Operator::Eq => continue,
+            };
+            let slack = self.new_temp_variable();
+            self.bounds.insert(slack, (0.0, f64::INFINITY));
+            let c = &mut self.constraints[index];
+            c.lhs += coefficient * slack;
+            c.operator = Operator::Eq;
+        }
+    }
+
+    pub fn standardize(&mut self) {
+        self.make_constraints_single_sided();
+        self.drop_negligible_terms();
+        self.make_objective_maximizing();
+        self.make_single_variable_constraints_bounds();
+        // terms that cancel out can leave constraints without any variables
+        self.eliminate_empty_constraints();
+        if self.presolve && !self.infeasible {
+            let size = (self.constraints.len(), self.substitutions.len());
+            presolve(self);
+            self.presolved = self.infeasible || (self.constraints.len(), self.substitutions.len()) != size;
+        }
+        self.make_all_constraints_equality_constraints();
+    }
+}
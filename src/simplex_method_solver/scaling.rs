@@ -0,0 +1,91 @@
+use super::sparse_matrix::SparseMatrix;
+
+// The number of times the rows and then the columns are scaled by the
+// geometric mean of their largest and smallest entries, which settles
+// within a few passes
+const GEOMETRIC_PASSES: usize = 4;
+
+// The factors every row and column of the matrix is multiplied by so that
+// its entries are close to one in size. Each factor is a power of two, so
+// scaling and unscaling lose nothing to round-off
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scaling {
+    pub rows: Vec<f64>,
+    pub columns: Vec<f64>,
+}
+
+// The factor that brings the smallest and largest of the magnitudes given
+// to the same distance from one, or one when there are none
+fn geometric_factor(magnitudes: impl Iterator<Item = f64>) -> f64 {
+    let (smallest, largest) = magnitudes
+        .fold((f64::INFINITY, 0.0), |(smallest, largest): (f64, f64), value| (smallest.min(value), largest.max(value)));
+    if largest > 0.0 {
+        1.0 / (smallest * largest).sqrt()
+    } else {
+        1.0
+    }
+}
+
+// The power of two nearest the factor
+fn power_of_two(factor: f64) -> f64 {
+    2f64.powi(factor.log2().round() as i32)
+}
+
+impl Scaling {
+    // Scales the rows and columns by the geometric means of their entries a
+    // few times over, which narrows the spread of magnitudes in the matrix,
+    // then equilibrates the columns so the largest entry of each is one
+    pub fn new(matrix: &SparseMatrix) -> Scaling {
+        let mut rows = vec![1.0; matrix.num_rows()];
+        let mut columns = vec![1.0; matrix.num_columns()];
+        for _ in 0..GEOMETRIC_PASSES {
+            for (row, factor) in rows.iter_mut().enumerate() {
+                *factor = geometric_factor(
+                    matrix.row(row).iter().map(|&(column, value)| (value * columns[column]).abs()),
+                );
+            }
+            for (column, factor) in columns.iter_mut().enumerate() {
+                *factor = geometric_factor(
+                    matrix.column(column).iter().map(|&(row, value)| (value * rows[row]).abs()),
+                );
+            }
+        }
+        for (column, factor) in columns.iter_mut().enumerate() {
+            let largest = matrix
+                .column(column)
+                .iter()
+                .map(|&(row, value)| (value * rows[row] * *factor).abs())
+                .fold(0.0, f64::max);
+            if largest > 0.0 {
+                *factor /= largest;
+            }
+        }
+        Scaling {
+            rows: rows.into_iter().map(power_of_two).collect(),
+            columns: columns.into_iter().map(power_of_two).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_narrows_the_spread_of_magnitudes() {
+        // a probability row and a currency row over the same two columns
+        let mut matrix = SparseMatrix::new(
+            2,
+            3,
+            [(0, 0, 1e-4), (0, 1, 3e-4), (1, 0, 2e6), (1, 1, 5e5), (1, 2, 1.0)],
+        );
+        let scaling = Scaling::new(&matrix);
+        for factor in scaling.rows.iter().chain(&scaling.columns) {
+            assert_eq!(factor.log2().fract(), 0.0);
+        }
+        matrix.scale(&scaling.rows, &scaling.columns);
+        for value in matrix.values() {
+            assert!((0.1..=2.0).contains(&value.abs()), "{} is badly scaled", value);
+        }
+    }
+}
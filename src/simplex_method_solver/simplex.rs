@@ -0,0 +1,1087 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+use crate::{
+    Algorithm, Basis, InitialBasis, Phase, Pivot, PivotRule, SolveStatus, SolverOptions, Variable,
+};
+
+use super::{
+    basis_representation::{BasisRepresentation, SingularBasis},
+    lu_factorization::LuFactorization,
+    pricing::Pricing,
+    sparse_matrix::SparseMatrix,
+    standard_form::{Ranging, SimplexOutcome, StandardForm},
+};
+
+// The number of pivots between recomputing the basic values from the right
+// hand sides, so round-off in their updates does not build up
+const RECOMPUTE_INTERVAL: usize = 50;
+
+// The primal and dual simplex methods for bounded variables, run over either
+// representation of the basis. The columns of the standard form are followed
+// by the artificial variables needed to find a starting basis, from
+// artificial_start on. The value of the basic variable at every position is
+// kept in values, and every nonbasic variable sits at the value kept for it in
+// nonbasic_values, which is one of its bounds or zero when it has neither.
+// The objective being optimised differs from the program's own costs while a
+// feasible basis is being found
+pub struct Simplex<'a, B> {
+    basis: B,
+    matrix: SparseMatrix,
+    variables: Vec<Variable<'a>>,
+    costs: Vec<f64>,
+    objective: Vec<f64>,
+    constant: f64,
+    rhs: Vec<f64>,
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+    artificial_start: usize,
+    is_basic: Vec<bool>,
+    values: Vec<f64>,
+    nonbasic_values: Vec<f64>,
+    largest_coefficient: f64,
+    initial_basis: InitialBasis,
+    pricing: Pricing,
+    phase: Phase,
+    objective_flipped: bool,
+    iterations: usize,
+    trace: Option<Vec<Pivot<'a>>>,
+    // the direction of every column along which the objective improves
+    // without limit, once the program is found to be unbounded
+    ray: Vec<f64>,
+    primal_tolerance: f64,
+    dual_tolerance: f64,
+    pivot_tolerance: f64,
+    iteration_limit: usize,
+    deadline: Option<Instant>,
+}
+
+// How far the entering variable can move before the basis has to change
+enum Step {
+    // the entering variable reaches its other bound, or covers the distance
+    // it is moving, first
+    BoundFlip,
+    // the basic variable at the position reaches the bound given
+    Pivot(usize, f64),
+}
+
+// The row of every column that has a single nonzero entry
+fn singleton_rows(matrix: &SparseMatrix) -> Vec<Option<usize>> {
+    (0..matrix.num_columns())
+        .map(|column| match matrix.column(column) {
+            [(row, _)] => Some(*row),
+            _ => None,
+        })
+        .collect()
+}
+
+// The value a nonbasic variable starts at, its lower bound if it has one
+fn starting_value(lower: f64, upper: f64) -> f64 {
+    if lower.is_finite() {
+        lower
+    } else if upper.is_finite() {
+        upper
+    } else {
+        0.0
+    }
+}
+
+// Finds the columns of an earlier basis in the program, leaving out any that
+// are no longer independent and completing it with a column of a single entry
+// for every row it does not cover, such as the slack of an added constraint
+fn find_warm_start_basis(form: &StandardForm, basis: &Basis) -> Option<Vec<usize>> {
+    let index = form
+        .variables
+        .iter()
+        .enumerate()
+        .map(|(column, var)| (*var, column))
+        .collect::<HashMap<_, _>>();
+    let candidates = basis
+        .variables
+        .iter()
+        .filter_map(|var| index.get(var).copied())
+        .collect::<Vec<_>>();
+    let (basis, uncovered) = complete_basis(form, candidates);
+    uncovered.is_empty().then_some(basis)
+}
+
+// Keeps the candidates that are independent of each other and adds a column
+// with a single entry for every row they do not cover, giving the columns
+// along with the rows that have no such column left
+fn complete_basis(form: &StandardForm, candidates: Vec<usize>) -> (Vec<usize>, Vec<usize>) {
+    let entries = candidates.iter().map(|&column| form.matrix.column(column)).collect::<Vec<_>>();
+    let (dependent, uncovered) = LuFactorization::dependent_columns(form.rhs.len(), &entries);
+
+    let mut basis = candidates
+        .into_iter()
+        .enumerate()
+        .filter(|(position, _)| !dependent.contains(position))
+        .map(|(_, column)| column)
+        .collect::<Vec<_>>();
+    let mut used = basis.iter().copied().collect::<HashSet<_>>();
+    let singleton_rows = singleton_rows(&form.matrix);
+    let mut missing = vec![];
+    for row in uncovered {
+        let column = (0..singleton_rows.len())
+            .find(|&column| singleton_rows[column] == Some(row) && !used.contains(&column));
+        match column {
+            Some(column) => {
+                used.insert(column);
+                basis.push(column);
+            }
+            None => missing.push(row),
+        }
+    }
+    (basis, missing)
+}
+
+// Whether a nonbasic variable at the value sits where the simplex method
+// keeps it, at one of its bounds or at zero when it has neither
+fn is_at_bound(value: f64, lower: f64, upper: f64) -> bool {
+    value == lower || value == upper || !lower.is_finite() && !upper.is_finite() && value == 0.0
+}
+
+// The value of a point for a nonbasic variable, taken to be at its bound, or
+// at zero when it has none, if it is within the tolerance of it
+fn snap_to_bound(value: f64, lower: f64, upper: f64, tolerance: f64) -> f64 {
+    let value = value.clamp(lower, upper);
+    if value - lower <= tolerance {
+        lower
+    } else if upper - value <= tolerance {
+        upper
+    } else if !lower.is_finite() && !upper.is_finite() && value.abs() <= tolerance {
+        0.0
+    } else {
+        value
+    }
+}
+
+// Picks a column with a single entry for every row, or gives up if some row
+// has none
+fn find_slack_basis(matrix: &SparseMatrix) -> Option<Vec<usize>> {
+    let mut basis = vec![None; matrix.num_rows()];
+    for (column, row) in singleton_rows(matrix).into_iter().enumerate() {
+        if let Some(row) = row {
+            basis[row].get_or_insert(column);
+        }
+    }
+    basis.into_iter().collect()
+}
+
+impl<'a, B: BasisRepresentation> Simplex<'a, B> {
+    // Starts from the earlier basis when given one, or from the columns with
+    // a single entry when running the dual simplex method, as long as the
+    // basis is either primal or dual feasible. Otherwise the rows without a
+    // ready made basic column are given an artificial variable
+    pub fn new(
+        form: &mut StandardForm<'a>,
+        options: &SolverOptions,
+        warm_start: Option<&Basis<'a>>,
+    ) -> Simplex<'a, B> {
+        let basis = match warm_start {
+            Some(basis) => find_warm_start_basis(form, basis),
+            None if options.algorithm == Algorithm::DualSimplex => find_slack_basis(&form.matrix),
+            None => None,
+        };
+        if let Some(basis) = basis {
+            let matrix = form.matrix.clone();
+            let mut simplex = Simplex::with_basis(form, options, matrix, basis, vec![]);
+            simplex.place_boxed_variables();
+            if simplex.is_primal_feasible() || simplex.is_dual_feasible() {
+                return simplex;
+            }
+        }
+
+        // what is left of each row with every variable at its starting value
+        let mut residuals = form.rhs.clone();
+        for column in 0..form.matrix.num_columns() {
+            let value = starting_value(form.lower[column], form.upper[column]);
+            for &(row, entry) in form.matrix.column(column) {
+                residuals[row] -= entry * value;
+            }
+        }
+
+        // a column with a single entry can make up the residual of its row if
+        // that keeps it within its bounds
+        let mut basis = vec![None; form.rhs.len()];
+        for (column, row) in singleton_rows(&form.matrix).into_iter().enumerate() {
+            let Some(row) = row else { continue };
+            let (lower, upper) = (form.lower[column], form.upper[column]);
+            let value = starting_value(lower, upper) + residuals[row] / form.matrix.column(column)[0].1;
+            if basis[row].is_none()
+                && value >= lower - options.primal_feasibility_tolerance
+                && value <= upper + options.primal_feasibility_tolerance
+            {
+                basis[row] = Some(column);
+            }
+        }
+
+        let mut artificial_columns = vec![];
+        let mut artificial_variables = vec![];
+        for row in 0..basis.len() {
+            if basis[row].is_none() {
+                let sign = if residuals[row] < 0.0 { -1.0 } else { 1.0 };
+                basis[row] = Some(form.matrix.num_columns() + artificial_columns.len());
+                artificial_columns.push(vec![(row, sign)]);
+                artificial_variables.push(form.new_temp_variable());
+            }
+        }
+        let matrix = form.matrix.with_columns(artificial_columns);
+        let basis = basis.into_iter().flatten().collect();
+        Simplex::with_basis(form, options, matrix, basis, artificial_variables)
+    }
+
+    // Crosses over from a point of the feasible region, such as the optimum
+    // the interior point method finds, to a basic solution. The columns away
+    // from their bounds make up the basis as far as they are independent,
+    // and each of the others that is away from its bounds is pushed to one of
+    // them with the basic variables making up for it, entering the basis
+    // instead if a basic variable reaches a bound first. A row that no column
+    // can cover keeps an artificial variable fixed at zero. Gives up if the
+    // point lies too far outside the feasible region for the basic solution
+    // to be feasible
+    pub fn crossover(
+        form: &mut StandardForm<'a>,
+        options: &SolverOptions,
+        point: &[f64],
+    ) -> Option<Simplex<'a, B>> {
+        let tolerance = options.primal_feasibility_tolerance;
+        let candidates = (0..form.variables.len())
+            .filter(|&column| {
+                let (lower, upper) = (form.lower[column], form.upper[column]);
+                !is_at_bound(snap_to_bound(point[column], lower, upper, tolerance), lower, upper)
+            })
+            .collect::<Vec<_>>();
+        let (mut basis, uncovered) = complete_basis(form, candidates);
+
+        let mut artificial_columns = vec![];
+        let mut artificial_variables = vec![];
+        for row in uncovered {
+            basis.push(form.matrix.num_columns() + artificial_columns.len());
+            artificial_columns.push(vec![(row, 1.0)]);
+            artificial_variables.push(form.new_temp_variable());
+        }
+        let matrix = form.matrix.with_columns(artificial_columns);
+        let mut simplex = Simplex::<B>::with_basis(form, options, matrix, basis, artificial_variables);
+        simplex.upper[simplex.artificial_start..].fill(0.0);
+        for (column, &value) in point.iter().enumerate() {
+            if !simplex.is_basic[column] {
+                simplex.nonbasic_values[column] =
+                    snap_to_bound(value, simplex.lower[column], simplex.upper[column], tolerance);
+            }
+        }
+        simplex.values = simplex.basic_values();
+
+        // the reduced costs choose which bound a boxed variable goes to, so
+        // moving it does not make the objective worse
+        let reduced_costs = simplex.basis.reduced_costs();
+        for (column, reduced_cost) in reduced_costs.into_iter().enumerate().take(simplex.artificial_start) {
+            let value = simplex.nonbasic_values[column];
+            let (lower, upper) = (simplex.lower[column], simplex.upper[column]);
+            if simplex.is_basic[column] || is_at_bound(value, lower, upper) {
+                continue;
+            }
+            let increase = if lower.is_finite() && upper.is_finite() {
+                match reduced_cost {
+                    cost if cost > simplex.dual_tolerance => true,
+                    cost if cost < -simplex.dual_tolerance => false,
+                    _ => upper - value < value - lower,
+                }
+            } else {
+                !lower.is_finite() && (upper.is_finite() || value < 0.0)
+            };
+            let (direction, target) = match increase {
+                true if upper.is_finite() => (1.0, upper),
+                true => (1.0, 0.0),
+                false if lower.is_finite() => (-1.0, lower),
+                false => (-1.0, 0.0),
+            };
+            let alpha = simplex.basis.column(column);
+            match simplex.step_towards(direction, &alpha, (target - value).abs()) {
+                Some(Step::Pivot(position, bound)) => simplex.pivot(position, column, alpha, bound).ok()?,
+                _ => simplex.move_nonbasic(column, target, &alpha),
+            }
+        }
+        simplex.values = simplex.basic_values();
+        simplex.is_primal_feasible().then_some(simplex)
+    }
+
+    // The columns hold those of the standard form followed by any artificial
+    // columns, which are bounded below by zero
+    fn with_basis(
+        form: &StandardForm<'a>,
+        options: &SolverOptions,
+        matrix: SparseMatrix,
+        basis: Vec<usize>,
+        artificial_variables: Vec<Variable<'a>>,
+    ) -> Simplex<'a, B> {
+        let artificial_start = form.variables.len();
+        let count = matrix.num_columns();
+        let mut costs = form.costs.clone();
+        costs.resize(count, 0.0);
+        let mut lower = form.lower.clone();
+        lower.resize(count, 0.0);
+        let mut upper = form.upper.clone();
+        upper.resize(count, f64::INFINITY);
+        let nonbasic_values = (0..count)
+            .map(|column| starting_value(lower[column], upper[column]))
+            .collect();
+        let largest_coefficient = form
+            .matrix
+            .values()
+            .chain(form.rhs.iter().copied())
+            .chain(form.costs.iter().copied())
+            .map(|value| value.abs())
+            .fold(1.0, f64::max);
+        let mut is_basic = vec![false; count];
+        basis.iter().for_each(|&column| is_basic[column] = true);
+
+        let mut variables = form.variables.clone();
+        variables.extend(artificial_variables);
+
+        let mut simplex = Simplex {
+            basis: B::new(matrix.clone(), basis),
+            matrix,
+            variables,
+            objective: vec![],
+            costs,
+            constant: form.constant,
+            rhs: form.rhs.clone(),
+            lower,
+            upper,
+            artificial_start,
+            is_basic,
+            values: vec![],
+            nonbasic_values,
+            largest_coefficient,
+            initial_basis: options.initial_basis,
+            pricing: Pricing::new(options.pivot_rule, count),
+            phase: Phase::Two,
+            objective_flipped: form.objective_flipped,
+            iterations: 0,
+            trace: options.record_trace.then(Vec::new),
+            ray: vec![],
+            primal_tolerance: options.primal_feasibility_tolerance,
+            dual_tolerance: options.dual_feasibility_tolerance,
+            pivot_tolerance: options.pivot_tolerance,
+            iteration_limit: options.iteration_limit,
+            deadline: form.deadline,
+        };
+        simplex.values = simplex.basic_values();
+        simplex.load_objective(simplex.costs.clone());
+        if options.pivot_rule == PivotRule::SteepestEdge {
+            simplex.initialise_edge_weights();
+        }
+        simplex
+    }
+
+    // B^-1 (b - N x_N), the values the basic variables take with every
+    // nonbasic variable at its value
+    fn basic_values(&mut self) -> Vec<f64> {
+        let mut residuals = self.rhs.clone();
+        for column in 0..self.matrix.num_columns() {
+            let value = self.nonbasic_values[column];
+            if !self.is_basic[column] && value != 0.0 {
+                for &(row, entry) in self.matrix.column(column) {
+                    residuals[row] -= entry * value;
+                }
+            }
+        }
+        self.basis.ftran(&residuals)
+    }
+
+    // Moves every nonbasic variable with two bounds to the one its reduced
+    // cost favours, which makes the basis dual feasible as far as they go
+    fn place_boxed_variables(&mut self) {
+        let reduced_costs = self.basis.reduced_costs();
+        for (column, reduced_cost) in reduced_costs.into_iter().enumerate().take(self.artificial_start) {
+            if !self.is_basic[column]
+                && self.lower[column].is_finite()
+                && self.upper[column].is_finite()
+            {
+                self.nonbasic_values[column] = if reduced_cost > 0.0 {
+                    self.upper[column]
+                } else {
+                    self.lower[column]
+                };
+            }
+        }
+        self.values = self.basic_values();
+    }
+
+    fn can_increase(&self, column: usize) -> bool {
+        self.nonbasic_values[column] < self.upper[column]
+    }
+
+    fn can_decrease(&self, column: usize) -> bool {
+        self.nonbasic_values[column] > self.lower[column]
+    }
+
+    // How far the basic variable at the position lies outside its bounds
+    fn infeasibility(&self, position: usize) -> f64 {
+        let column = self.basis.basis()[position];
+        let value = self.values[position];
+        f64::max(self.lower[column] - value, value - self.upper[column]).max(0.0)
+    }
+
+    fn is_primal_feasible(&self) -> bool {
+        (0..self.values.len())
+            .all(|position| self.infeasibility(position) <= self.primal_tolerance)
+    }
+
+    fn is_dual_feasible(&mut self) -> bool {
+        let reduced_costs = self.basis.reduced_costs();
+        (0..self.artificial_start).all(|column| {
+            self.is_basic[column]
+                || !(reduced_costs[column] > self.dual_tolerance && self.can_increase(column)
+                    || reduced_costs[column] < -self.dual_tolerance && self.can_decrease(column))
+        })
+    }
+
+    // Whether there are artificial variables left to drive to zero, which
+    // those fixed at zero by crossing over are not
+    fn has_artificial_variables(&self) -> bool {
+        self.upper[self.artificial_start..].iter().any(|&upper| upper > 0.0)
+    }
+
+    // The exact edge lengths are found once from the starting basis and kept
+    // up to date from then on
+    fn initialise_edge_weights(&mut self) {
+        for column in 0..self.costs.len() {
+            if !self.is_basic[column] {
+                let length = self
+                    .basis
+                    .column(column)
+                    .iter()
+                    .map(|entry| entry * entry)
+                    .sum::<f64>();
+                self.pricing.set_weight(column, 1.0 + length);
+            }
+        }
+    }
+
+    // Replaces the objective being optimised
+    fn load_objective(&mut self, objective: Vec<f64>) {
+        self.basis.set_costs(&objective);
+        self.objective = objective;
+    }
+
+    // The value of every column
+    fn column_values(&self) -> Vec<f64> {
+        let mut values = self.nonbasic_values.clone();
+        for (&column, &value) in self.basis.basis().iter().zip(&self.values) {
+            values[column] = value;
+        }
+        values
+    }
+
+    // The value of the objective currently being optimised, which is the sum
+    // of the artificial variables during phase I
+    fn objective_value(&self) -> f64 {
+        let value = self
+            .column_values()
+            .iter()
+            .zip(&self.objective)
+            .map(|(value, cost)| cost * value)
+            .sum::<f64>();
+        match self.phase {
+            Phase::One => -value,
+            Phase::Two if self.objective_flipped => -(value + self.constant),
+            Phase::Two => value + self.constant,
+        }
+    }
+
+    // Chooses the column to enter the basis by the pivot rule along with its
+    // reduced cost, whose sign gives the direction it moves in. Artificial
+    // variables are never brought back in once they have left
+    fn entering_column(&mut self) -> Result<Option<(usize, f64)>, SolveStatus> {
+        let reduced_costs = self.basis.reduced_costs();
+        if reduced_costs.iter().any(|cost| !cost.is_finite()) {
+            return Err(SolveStatus::NumericalTrouble);
+        }
+        let entering = self.pricing.choose_entering(
+            (0..self.artificial_start)
+                .filter(|&column| {
+                    !self.is_basic[column]
+                        && (reduced_costs[column] > self.dual_tolerance && self.can_increase(column)
+                            || reduced_costs[column] < -self.dual_tolerance && self.can_decrease(column))
+                })
+                .map(|column| (column, reduced_costs[column])),
+        );
+        Ok(entering.map(|column| (column, reduced_costs[column])))
+    }
+
+    // Finds how far the entering column can move in the direction given
+    // before a basic variable reaches one of its bounds or the entering
+    // variable reaches its other bound, given the column in terms of the basis
+    fn ratio_test(&self, column: usize, direction: f64, alpha: &[f64]) -> Option<Step> {
+        self.step_towards(direction, alpha, self.upper[column] - self.lower[column])
+    }
+
+    // Finds whether a basic variable reaches one of its bounds before the
+    // entering column has moved the distance given in the direction given
+    fn step_towards(&self, direction: f64, alpha: &[f64], range: f64) -> Option<Step> {
+        let mut leaving: Option<(usize, f64)> = None;
+        for (position, &entry) in alpha.iter().enumerate() {
+            let rate = direction * entry;
+            if rate.abs() <= self.pivot_tolerance {
+                continue;
+            }
+            let basic = self.basis.basis()[position];
+            let bound = if rate > 0.0 { self.lower[basic] } else { self.upper[basic] };
+            if !bound.is_finite() {
+                continue;
+            }
+            let ratio = ((self.values[position] - bound) / rate).max(0.0);
+            leaving = match leaving {
+                Some((best, best_ratio))
+                    if ratio > best_ratio + self.pivot_tolerance
+                        || (ratio >= best_ratio - self.pivot_tolerance
+                            && !self.breaks_tie(position, best, alpha)) =>
+                {
+                    Some((best, best_ratio))
+                }
+                _ => Some((position, ratio)),
+            };
+        }
+
+        match leaving {
+            Some((_, ratio)) if range <= ratio => Some(Step::BoundFlip),
+            Some((position, _)) => {
+                let basic = self.basis.basis()[position];
+                let bound = if direction * alpha[position] > 0.0 {
+                    self.lower[basic]
+                } else {
+                    self.upper[basic]
+                };
+                Some(Step::Pivot(position, bound))
+            }
+            None if range.is_finite() => Some(Step::BoundFlip),
+            None => None,
+        }
+    }
+
+    // Bland's rule takes the lowest indexed variable out of the basis on a tie,
+    // the other rules take the largest pivot element as it is the most stable
+    fn breaks_tie(&self, position: usize, other: usize, alpha: &[f64]) -> bool {
+        match self.pricing.rule() {
+            PivotRule::Bland => self.basis.basis()[position] < self.basis.basis()[other],
+            _ => alpha[position].abs() > alpha[other].abs(),
+        }
+    }
+
+    // Updates the pricing weights for a pivot, from the pivot row and the
+    // entering column in terms of the basis before the pivot is made
+    fn update_weights(&mut self, position: usize, entering: usize, leaving: usize, alpha: &[f64]) {
+        let pivot = alpha[position];
+        match self.pricing.rule() {
+            PivotRule::SteepestEdge => {
+                let row = self.basis.row(position);
+                let products = self.basis.column_products(alpha);
+                let entering_weight = 1.0 + alpha.iter().map(|entry| entry * entry).sum::<f64>();
+                let columns = (0..row.len())
+                    .filter(|&column| !self.is_basic[column])
+                    .map(|column| (column, row[column] / pivot, products[column]))
+                    .collect::<Vec<_>>();
+                self.pricing.update_steepest_edge(
+                    entering,
+                    leaving,
+                    pivot,
+                    entering_weight,
+                    columns.into_iter(),
+                );
+            }
+            PivotRule::Devex => {
+                let row = self.basis.row(position);
+                let ratios = (0..row.len())
+                    .filter(|&column| !self.is_basic[column] || column == leaving)
+                    .map(|column| (column, row[column] / pivot))
+                    .collect::<Vec<_>>();
+                self.pricing.update_devex(entering, leaving, ratios.into_iter());
+            }
+            PivotRule::Dantzig | PivotRule::Bland => {}
+        }
+    }
+
+    fn record(&mut self, entering: usize, leaving: usize) {
+        let objective_value = self.objective_value();
+        if let Some(trace) = &mut self.trace {
+            trace.push(Pivot {
+                phase: self.phase,
+                entering: self.variables[entering],
+                leaving: self.variables[leaving],
+                objective_value,
+            });
+        }
+    }
+
+    // Moves the nonbasic column to the value given, updating the basic
+    // variables through the column in terms of the basis
+    fn move_nonbasic(&mut self, column: usize, value: f64, alpha: &[f64]) {
+        let step = value - self.nonbasic_values[column];
+        for (basic, entry) in self.values.iter_mut().zip(alpha) {
+            *basic -= step * entry;
+        }
+        self.nonbasic_values[column] = value;
+    }
+
+    // Brings the column into the basis at the position, moving it just far
+    // enough for the variable leaving to reach the value given
+    fn pivot(
+        &mut self,
+        position: usize,
+        column: usize,
+        alpha: Vec<f64>,
+        leaving_value: f64,
+    ) -> Result<(), SingularBasis> {
+        let leaving = self.basis.basis()[position];
+        self.update_weights(position, column, leaving, &alpha);
+
+        let step = (self.values[position] - leaving_value) / alpha[position];
+        for (value, entry) in self.values.iter_mut().zip(&alpha) {
+            *value -= step * entry;
+        }
+        self.values[position] = self.nonbasic_values[column] + step;
+        self.nonbasic_values[leaving] = leaving_value;
+        self.basis.pivot(position, column, &alpha)?;
+        self.is_basic[leaving] = false;
+        self.is_basic[column] = true;
+        self.record(column, leaving);
+        Ok(())
+    }
+
+    // Round-off shows up as values that are no longer finite or as basic
+    // variables that have drifted outside their bounds
+    fn has_numerical_trouble(&self) -> bool {
+        self.values.iter().any(|value| !value.is_finite())
+            || (0..self.values.len())
+                .any(|position| self.infeasibility(position) > self.primal_tolerance)
+    }
+
+    // Whether the iteration limit or the time limit stops the method from
+    // making another pivot, and which of them
+    fn reached_limit(&self) -> Option<SolveStatus> {
+        if self.iterations >= self.iteration_limit {
+            Some(SolveStatus::IterationLimit)
+        } else if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            Some(SolveStatus::TimeLimit)
+        } else {
+            None
+        }
+    }
+
+    // Counts an iteration, recomputing the basic values now and again
+    fn finish_iteration(&mut self) {
+        self.iterations += 1;
+        if self.iterations % RECOMPUTE_INTERVAL == 0 {
+            self.values = self.basic_values();
+        }
+    }
+
+    // Pivots until no column can improve the objective or a limit stops it.
+    // An entering variable that reaches its other bound before any basic
+    // variable reaches one of its own moves there without changing the basis
+    fn iterate(&mut self) -> SolveStatus {
+        loop {
+            let (column, reduced_cost) = match self.entering_column() {
+                Ok(Some(entering)) => entering,
+                Ok(None) => return SolveStatus::Optimal,
+                Err(status) => return status,
+            };
+            if let Some(status) = self.reached_limit() {
+                return status;
+            }
+            let direction = reduced_cost.signum();
+            let alpha = self.basis.column(column);
+            match self.ratio_test(column, direction, &alpha) {
+                None => {
+                    self.ray = self.improving_ray(column, direction, &alpha);
+                    return SolveStatus::Unbounded;
+                }
+                Some(Step::BoundFlip) => {
+                    let bound = if direction > 0.0 {
+                        self.upper[column]
+                    } else {
+                        self.lower[column]
+                    };
+                    self.move_nonbasic(column, bound, &alpha);
+                    self.record(column, column);
+                }
+                Some(Step::Pivot(position, bound)) => {
+                    if self.pivot(position, column, alpha, bound).is_err() {
+                        return SolveStatus::NumericalTrouble;
+                    }
+                }
+            }
+            self.finish_iteration();
+            if self.has_numerical_trouble() {
+                return SolveStatus::NumericalTrouble;
+            }
+        }
+    }
+
+    // The direction the entering column moves the columns in, with the basic
+    // variables making up for it so every row keeps holding
+    fn improving_ray(&self, column: usize, direction: f64, alpha: &[f64]) -> Vec<f64> {
+        let mut ray = vec![0.0; self.artificial_start];
+        ray[column] = direction;
+        for (&basic, entry) in self.basis.basis().iter().zip(alpha) {
+            if basic < self.artificial_start {
+                ray[basic] = -direction * entry;
+            }
+        }
+        ray
+    }
+
+    // Chooses the basic variable furthest outside its bounds to leave the
+    // basis, or the lowest indexed one under Bland's rule, along with the
+    // bound it leaves at
+    fn leaving_position_dual(&self) -> Option<(usize, f64)> {
+        let candidates = (0..self.values.len())
+            .filter(|&position| self.infeasibility(position) > self.primal_tolerance);
+        let position = match self.pricing.rule() {
+            PivotRule::Bland => candidates.min_by_key(|&position| self.basis.basis()[position]),
+            _ => candidates
+                .max_by(|&a, &b| self.infeasibility(a).total_cmp(&self.infeasibility(b))),
+        }?;
+        let column = self.basis.basis()[position];
+        if self.values[position] < self.lower[column] {
+            Some((position, self.lower[column]))
+        } else {
+            Some((position, self.upper[column]))
+        }
+    }
+
+    // The bound flipping ratio test. Each nonbasic column that can move the
+    // leaving variable towards its bound becomes a breakpoint at the ratio of
+    // its reduced cost to its entry in the pivot row. Passing a breakpoint
+    // flips its variable to the other bound, which is worth doing while the
+    // leaving variable is still short of its bound afterwards. The column at
+    // the first breakpoint that cannot be passed enters the basis, and the
+    // columns passed on the way are returned to be flipped
+    fn entering_column_dual(
+        &self,
+        reduced_costs: &[f64],
+        row: &[f64],
+        position: usize,
+        target: f64,
+    ) -> Option<(usize, Vec<usize>)> {
+        // the leaving variable changes by -row[j] for every unit x_j moves
+        let increase = target > self.values[position];
+        let mut breakpoints = (0..self.artificial_start)
+            .filter(|&column| !self.is_basic[column] && row[column].abs() > self.pivot_tolerance)
+            .filter_map(|column| {
+                let direction = if (row[column] < 0.0) == increase { 1.0 } else { -1.0 };
+                let movable = if direction > 0.0 {
+                    self.can_increase(column)
+                } else {
+                    self.can_decrease(column)
+                };
+                let ratio = (-reduced_costs[column] * direction).max(0.0) / row[column].abs();
+                movable.then_some((column, ratio))
+            })
+            .collect::<Vec<_>>();
+        breakpoints.sort_by(|(a, a_ratio), (b, b_ratio)| {
+            a_ratio.total_cmp(b_ratio).then_with(|| match self.pricing.rule() {
+                PivotRule::Bland => a.cmp(b),
+                _ => row[*b].abs().total_cmp(&row[*a].abs()),
+            })
+        });
+
+        let mut slope = (target - self.values[position]).abs();
+        let mut flips = vec![];
+        for (column, _) in breakpoints {
+            let range = self.upper[column] - self.lower[column];
+            let remaining = slope - row[column].abs() * range;
+            if remaining > self.primal_tolerance {
+                slope = remaining;
+                flips.push(column);
+            } else {
+                return Some((column, flips));
+            }
+        }
+        None
+    }
+
+    // Moves each of the nonbasic columns to its other bound together
+    fn flip_bounds(&mut self, columns: &[usize]) {
+        let mut change = vec![0.0; self.rhs.len()];
+        for &column in columns {
+            let value = if self.nonbasic_values[column] == self.lower[column] {
+                self.upper[column]
+            } else {
+                self.lower[column]
+            };
+            for &(row, entry) in self.matrix.column(column) {
+                change[row] += entry * (value - self.nonbasic_values[column]);
+            }
+            self.nonbasic_values[column] = value;
+        }
+        let change = self.basis.ftran(&change);
+        for (value, entry) in self.values.iter_mut().zip(change) {
+            *value -= entry;
+        }
+    }
+
+    // Pivots until every basic variable is within its bounds or a limit
+    // stops it, keeping the reduced costs optimal along the way
+    fn iterate_dual(&mut self) -> SolveStatus {
+        loop {
+            let Some((position, target)) = self.leaving_position_dual() else {
+                return SolveStatus::Optimal;
+            };
+            if let Some(status) = self.reached_limit() {
+                return status;
+            }
+            let reduced_costs = self.basis.reduced_costs();
+            let row = self.basis.row(position);
+            if reduced_costs.iter().chain(&row).any(|value| !value.is_finite()) {
+                return SolveStatus::NumericalTrouble;
+            }
+            // nothing can bring the variable back within its bounds
+            let Some((column, flips)) =
+                self.entering_column_dual(&reduced_costs, &row, position, target)
+            else {
+                return SolveStatus::Infeasible;
+            };
+
+            if !flips.is_empty() {
+                self.flip_bounds(&flips);
+            }
+            let alpha = self.basis.column(column);
+            if self.pivot(position, column, alpha, target).is_err() {
+                return SolveStatus::NumericalTrouble;
+            }
+            self.finish_iteration();
+            if self.values.iter().any(|value| !value.is_finite()) {
+                return SolveStatus::NumericalTrouble;
+            }
+        }
+    }
+
+    // The largest value of an artificial variable still in the basis
+    fn largest_artificial_value(&self) -> f64 {
+        self.basis
+            .basis()
+            .iter()
+            .zip(&self.values)
+            .filter(|(&column, _)| column >= self.artificial_start)
+            .map(|(_, &value)| value)
+            .fold(0.0, f64::max)
+    }
+
+    // Phase I finds a feasible basis by driving the sum of the artificial
+    // variables to zero, temporarily replacing the objective with one that
+    // penalises every artificial variable
+    fn solve_phase_one(&mut self) -> SolveStatus {
+        let mut objective = vec![0.0; self.costs.len()];
+        objective[self.artificial_start..].fill(-1.0);
+        self.phase = Phase::One;
+        self.load_objective(objective);
+
+        let status = self.iterate();
+        if status != SolveStatus::Optimal {
+            return status;
+        }
+        if self.objective_value() > self.primal_tolerance {
+            return SolveStatus::Infeasible;
+        }
+        self.drive_out_artificial_variables()
+    }
+
+    // The Big-M method optimises the objective directly with every artificial
+    // variable given a cost of -M, so they are pushed out of the basis along
+    // the way. If any artificial variable is left above zero M was not large
+    // enough to settle feasibility, and phase I is used to decide it instead
+    fn solve_big_m(&mut self, m: Option<f64>) -> SolveStatus {
+        // M is taken to dwarf every coefficient in the program
+        let m = m.unwrap_or(100.0 * self.largest_coefficient);
+        let mut objective = self.costs.clone();
+        objective[self.artificial_start..].fill(-m);
+        self.phase = Phase::Two;
+        self.load_objective(objective);
+
+        let status = self.iterate();
+        if status != SolveStatus::Optimal && status != SolveStatus::Unbounded {
+            return status;
+        }
+        if self.largest_artificial_value() > self.primal_tolerance {
+            return self.solve_phase_one();
+        }
+        self.drive_out_artificial_variables()
+    }
+
+    // Pivots the artificial variables still in the basis at zero out of it.
+    // Where that is impossible the row is redundant, and its artificial
+    // variable stays in the basis at zero without affecting the rest. Every
+    // artificial variable is then fixed at zero
+    fn drive_out_artificial_variables(&mut self) -> SolveStatus {
+        for position in 0..self.values.len() {
+            if self.basis.basis()[position] < self.artificial_start {
+                continue;
+            }
+            let row = self.basis.row(position);
+            let column = (0..self.artificial_start)
+                .filter(|&column| !self.is_basic[column] && row[column].abs() > self.pivot_tolerance)
+                .max_by(|&a, &b| row[a].abs().total_cmp(&row[b].abs()));
+            if let Some(column) = column {
+                let alpha = self.basis.column(column);
+                if self.pivot(position, column, alpha, 0.0).is_err() {
+                    return SolveStatus::NumericalTrouble;
+                }
+            }
+        }
+        self.upper[self.artificial_start..].fill(0.0);
+        SolveStatus::Optimal
+    }
+
+    // The interval of steps the basic variables can take in the direction
+    // given while staying within their bounds
+    fn step_range(&self, direction: &[f64]) -> (f64, f64) {
+        let (mut lower, mut upper) = (f64::NEG_INFINITY, f64::INFINITY);
+        for (position, &rate) in direction.iter().enumerate() {
+            if rate.abs() <= self.pivot_tolerance {
+                continue;
+            }
+            let column = self.basis.basis()[position];
+            let to_lower = (self.lower[column] - self.values[position]) / rate;
+            let to_upper = (self.upper[column] - self.values[position]) / rate;
+            let (low, high) = if rate > 0.0 { (to_lower, to_upper) } else { (to_upper, to_lower) };
+            lower = lower.max(low);
+            upper = upper.min(high);
+        }
+        (lower.min(0.0), upper.max(0.0))
+    }
+
+    // The changes to each cost that keep every reduced cost optimal. The
+    // cost of a nonbasic column only moves its own reduced cost, while the
+    // cost of a basic column moves the reduced cost of every nonbasic column
+    // by its entry in the row of the basic column
+    fn cost_ranges(&mut self) -> Vec<(f64, f64)> {
+        let reduced_costs = self.basis.reduced_costs();
+        let mut ranges = vec![(f64::NEG_INFINITY, f64::INFINITY); self.artificial_start];
+        for (column, range) in ranges.iter_mut().enumerate() {
+            if !self.is_basic[column] && self.can_increase(column) {
+                range.1 = (-reduced_costs[column]).max(0.0);
+            }
+            if !self.is_basic[column] && self.can_decrease(column) {
+                range.0 = (-reduced_costs[column]).min(0.0);
+            }
+        }
+        for position in 0..self.values.len() {
+            let column = self.basis.basis()[position];
+            if column >= self.artificial_start {
+                continue;
+            }
+            let row = self.basis.row(position);
+            let (mut lower, mut upper) = (f64::NEG_INFINITY, f64::INFINITY);
+            for other in 0..self.artificial_start {
+                if self.is_basic[other] || row[other].abs() <= self.pivot_tolerance {
+                    continue;
+                }
+                let ratio = reduced_costs[other] / row[other];
+                // the reduced cost has to stay at or below zero while the
+                // variable can increase and at or above zero while it can decrease
+                if self.can_increase(other) {
+                    if row[other] > 0.0 {
+                        lower = lower.max(ratio);
+                    } else {
+                        upper = upper.min(ratio);
+                    }
+                }
+                if self.can_decrease(other) {
+                    if row[other] > 0.0 {
+                        upper = upper.min(ratio);
+                    } else {
+                        lower = lower.max(ratio);
+                    }
+                }
+            }
+            ranges[column] = (lower.min(0.0), upper.max(0.0));
+        }
+        ranges
+    }
+
+    // How far the costs, the right hand sides and the nonbasic variables can
+    // move before the basis stops being optimal
+    fn ranging(&mut self) -> Ranging {
+        let costs = self.cost_ranges();
+        let rows = self.rhs.len();
+        let rhs = (0..rows)
+            .map(|row| {
+                let mut unit = vec![0.0; rows];
+                unit[row] = 1.0;
+                let direction = self.basis.ftran(&unit);
+                self.step_range(&direction)
+            })
+            .collect();
+        let columns = (0..self.artificial_start)
+            .map(|column| {
+                if self.is_basic[column] {
+                    return (0.0, 0.0);
+                }
+                let direction = self.basis.column(column).iter().map(|entry| -entry).collect::<Vec<_>>();
+                self.step_range(&direction)
+            })
+            .collect();
+        Ranging { costs, rhs, columns }
+    }
+
+    fn run(&mut self) -> SolveStatus {
+        if self.has_artificial_variables() {
+            let status = match self.initial_basis {
+                InitialBasis::TwoPhase => self.solve_phase_one(),
+                InitialBasis::BigM(m) => self.solve_big_m(m),
+            };
+            if status != SolveStatus::Optimal {
+                return status;
+            }
+        }
+        self.phase = Phase::Two;
+        self.load_objective(self.costs.clone());
+        if !self.is_primal_feasible() {
+            let status = self.iterate_dual();
+            if status != SolveStatus::Optimal {
+                return status;
+            }
+        }
+        self.iterate()
+    }
+
+    pub fn solve(mut self) -> SimplexOutcome<'a> {
+        let status = self.run();
+        let mut values = self.column_values();
+        values.truncate(self.artificial_start);
+        // phase I ending above zero is optimal for the sum of the artificial
+        // variables, and its duals prove the program infeasible
+        let proves_infeasible = status == SolveStatus::Infeasible && self.phase == Phase::One;
+        let (duals, reduced_costs, ranging) = if status == SolveStatus::Optimal || proves_infeasible {
+            let mut reduced_costs = self.basis.reduced_costs();
+            reduced_costs.truncate(self.artificial_start);
+            let ranging = (status == SolveStatus::Optimal).then(|| self.ranging());
+            (self.basis.duals(), reduced_costs, ranging)
+        } else {
+            (vec![], vec![], None)
+        };
+        let basis = (status == SolveStatus::Optimal).then(|| Basis {
+            variables: self
+                .basis
+                .basis()
+                .iter()
+                .filter(|&&column| column < self.artificial_start)
+                .map(|&column| self.variables[column])
+                .collect(),
+        });
+        SimplexOutcome {
+            status,
+            values,
+            duals,
+            reduced_costs,
+            ranging,
+            ray: self.ray,
+            basis,
+            iterations: self.iterations,
+            trace: self.trace.unwrap_or_default(),
+        }
+    }
+}
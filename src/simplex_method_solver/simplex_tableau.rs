@@ -0,0 +1,181 @@
+use super::{
+    basis_representation::{BasisRepresentation, SingularBasis},
+    sparse_matrix::SparseMatrix,
+};
+
+// The full tableau, pivoted in its entirety on every change of basis. The
+// objective row holds the reduced cost of every column and the rows below it
+// hold B^-1 A, one row per basic position. The columns of A are followed by
+// those of B^-1, which start out as the identity and are pivoted along with
+// the rest. Each row keeps only its nonzero entries in order of column, so
+// the parts of the tableau that stay sparse are never filled in with zeros
+pub struct SimplexTableau {
+    objective: Vec<f64>,
+    rows: Vec<Vec<(usize, f64)>>,
+    columns: usize,
+    basis: Vec<usize>,
+}
+
+// The entry of a sparse row in the given column
+fn entry(row: &[(usize, f64)], column: usize) -> f64 {
+    row.binary_search_by_key(&column, |&(column, _)| column)
+        .map_or(0.0, |index| row[index].1)
+}
+
+// row - factor * pivot_row, merging the entries of both in order of column
+fn subtract_multiple(row: &[(usize, f64)], factor: f64, pivot_row: &[(usize, f64)]) -> Vec<(usize, f64)> {
+    let mut result = Vec::with_capacity(row.len() + pivot_row.len());
+    let (mut i, mut j) = (0, 0);
+    while i < row.len() || j < pivot_row.len() {
+        let (column, value) = match (row.get(i), pivot_row.get(j)) {
+            (Some(&(a, x)), Some(&(b, y))) if a == b => {
+                i += 1;
+                j += 1;
+                (a, x - factor * y)
+            }
+            (Some(&(a, x)), Some(&(b, _))) if a < b => {
+                i += 1;
+                (a, x)
+            }
+            (Some(&(a, x)), None) => {
+                i += 1;
+                (a, x)
+            }
+            (_, Some(&(b, y))) => {
+                j += 1;
+                (b, -factor * y)
+            }
+            (None, None) => unreachable!(),
+        };
+        if value != 0.0 {
+            result.push((column, value));
+        }
+    }
+    result
+}
+
+impl SimplexTableau {
+    // Clears the reduced costs of the basic columns from the objective row
+    fn price_out_basis(&mut self) {
+        for (index, &column) in self.basis.iter().enumerate() {
+            let factor = self.objective[column];
+            if factor != 0.0 {
+                for &(j, value) in &self.rows[index] {
+                    self.objective[j] -= factor * value;
+                }
+            }
+        }
+    }
+}
+
+impl BasisRepresentation for SimplexTableau {
+    // Pivots each basic column in on the free row where it is largest
+    fn new(matrix: SparseMatrix, basis: Vec<usize>) -> Self {
+        let (rows, columns) = (matrix.num_rows(), matrix.num_columns());
+        let mut simplex_tableau = SimplexTableau {
+            objective: vec![0.0; columns + rows],
+            rows: (0..rows)
+                .map(|row| {
+                    let mut entries = matrix.row(row).to_vec();
+                    entries.push((columns + row, 1.0));
+                    entries
+                })
+                .collect(),
+            columns,
+            basis: vec![usize::MAX; rows],
+        };
+        for column in basis {
+            let position = (0..rows)
+                .filter(|&position| simplex_tableau.basis[position] == usize::MAX)
+                .max_by(|&a, &b| {
+                    let rows = &simplex_tableau.rows;
+                    entry(&rows[a], column).abs().total_cmp(&entry(&rows[b], column).abs())
+                })
+                .unwrap();
+            simplex_tableau.pivot(position, column, &[]).unwrap();
+        }
+        simplex_tableau
+    }
+
+    fn basis(&self) -> &[usize] {
+        &self.basis
+    }
+
+    fn set_costs(&mut self, costs: &[f64]) {
+        for (j, cost) in self.objective.iter_mut().enumerate() {
+            *cost = costs.get(j).copied().unwrap_or(0.0);
+        }
+        self.price_out_basis();
+    }
+
+    fn reduced_costs(&mut self) -> Vec<f64> {
+        self.objective[..self.columns].to_vec()
+    }
+
+    // The objective row holds -c_B^T B^-1 under the columns of B^-1
+    fn duals(&mut self) -> Vec<f64> {
+        self.objective[self.columns..].iter().map(|value| -value).collect()
+    }
+
+    fn column(&mut self, column: usize) -> Vec<f64> {
+        self.rows.iter().map(|row| entry(row, column)).collect()
+    }
+
+    fn row(&mut self, position: usize) -> Vec<f64> {
+        let mut row = vec![0.0; self.columns];
+        for &(column, value) in self.rows[position].iter().take_while(|&&(column, _)| column < self.columns) {
+            row[column] = value;
+        }
+        row
+    }
+
+    fn ftran(&mut self, vector: &[f64]) -> Vec<f64> {
+        self.rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .skip_while(|&&(column, _)| column < self.columns)
+                    .map(|&(column, value)| value * vector[column - self.columns])
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn column_products(&mut self, vector: &[f64]) -> Vec<f64> {
+        let mut products = vec![0.0; self.columns];
+        for (row, &factor) in self.rows.iter().zip(vector) {
+            if factor != 0.0 {
+                for &(column, value) in row.iter().take_while(|&&(column, _)| column < self.columns) {
+                    products[column] += value * factor;
+                }
+            }
+        }
+        products
+    }
+
+    fn pivot(&mut self, position: usize, column: usize, _alpha: &[f64]) -> Result<(), SingularBasis> {
+        let pivot = entry(&self.rows[position], column);
+        if pivot == 0.0 {
+            return Err(SingularBasis);
+        }
+        let pivot_row = self.rows[position]
+            .iter()
+            .map(|&(j, value)| (j, value / pivot))
+            .collect::<Vec<_>>();
+        for (index, row) in self.rows.iter_mut().enumerate() {
+            let factor = entry(row, column);
+            if index != position && factor != 0.0 {
+                *row = subtract_multiple(row, factor, &pivot_row);
+            }
+        }
+        let factor = self.objective[column];
+        if factor != 0.0 {
+            for &(j, value) in &pivot_row {
+                self.objective[j] -= factor * value;
+            }
+        }
+        self.rows[position] = pivot_row;
+        self.basis[position] = column;
+        Ok(())
+    }
+}
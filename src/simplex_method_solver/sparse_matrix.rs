@@ -0,0 +1,160 @@
+// A matrix kept as its nonzero entries twice over, compressed by column and
+// compressed by row, so that both its columns and its rows can be walked
+// without touching any of the zeros. The entries of column j are
+// column_entries[column_starts[j]..column_starts[j + 1]] as (row, value)
+// pairs in order of row, and likewise the entries of row i are
+// row_entries[row_starts[i]..row_starts[i + 1]] as (column, value) pairs
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseMatrix {
+    rows: usize,
+    column_starts: Vec<usize>,
+    column_entries: Vec<(usize, f64)>,
+    row_starts: Vec<usize>,
+    row_entries: Vec<(usize, f64)>,
+}
+
+impl SparseMatrix {
+    // Builds the matrix from (row, column, value) entries in any order,
+    // adding up entries given more than once and leaving out zeros
+    pub fn new(
+        rows: usize,
+        columns: usize,
+        entries: impl IntoIterator<Item = (usize, usize, f64)>,
+    ) -> SparseMatrix {
+        let mut entries = entries.into_iter().collect::<Vec<_>>();
+        entries.sort_by_key(|&(row, column, _)| (column, row));
+        let mut by_column = vec![vec![]; columns];
+        for (row, column, value) in entries {
+            let entries: &mut Vec<(usize, f64)> = &mut by_column[column];
+            match entries.last_mut() {
+                Some((last, sum)) if *last == row => *sum += value,
+                _ => entries.push((row, value)),
+            }
+        }
+        SparseMatrix::from_columns(rows, by_column)
+    }
+
+    // Builds the matrix from the entries of each of its columns, which must
+    // be in order of row without repeats
+    pub fn from_columns(rows: usize, columns: Vec<Vec<(usize, f64)>>) -> SparseMatrix {
+        let mut column_starts = Vec::with_capacity(columns.len() + 1);
+        let mut column_entries = vec![];
+        let mut row_counts = vec![0; rows];
+        for entries in columns {
+            column_starts.push(column_entries.len());
+            for (row, value) in entries.into_iter().filter(|&(_, value)| value != 0.0) {
+                row_counts[row] += 1;
+                column_entries.push((row, value));
+            }
+        }
+        column_starts.push(column_entries.len());
+
+        // walking the columns in order fills each row in order of column
+        let mut row_starts = Vec::with_capacity(rows + 1);
+        row_starts.push(0);
+        for count in &row_counts {
+            row_starts.push(row_starts.last().unwrap() + count);
+        }
+        let mut next = row_starts[..rows].to_vec();
+        let mut row_entries = vec![(0, 0.0); column_entries.len()];
+        for column in 0..column_starts.len() - 1 {
+            for &(row, value) in &column_entries[column_starts[column]..column_starts[column + 1]] {
+                row_entries[next[row]] = (column, value);
+                next[row] += 1;
+            }
+        }
+
+        SparseMatrix {
+            rows,
+            column_starts,
+            column_entries,
+            row_starts,
+            row_entries,
+        }
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.column_starts.len() - 1
+    }
+
+    // The nonzero entries of the column as (row, value) pairs
+    pub fn column(&self, column: usize) -> &[(usize, f64)] {
+        &self.column_entries[self.column_starts[column]..self.column_starts[column + 1]]
+    }
+
+    // The nonzero entries of the row as (column, value) pairs
+    pub fn row(&self, row: usize) -> &[(usize, f64)] {
+        &self.row_entries[self.row_starts[row]..self.row_starts[row + 1]]
+    }
+
+    // Every nonzero value in the matrix
+    pub fn values(&self) -> impl Iterator<Item = f64> + '_ {
+        self.column_entries.iter().map(|&(_, value)| value)
+    }
+
+    // Multiplies every entry by the factor of its row and of its column
+    pub fn scale(&mut self, row_factors: &[f64], column_factors: &[f64]) {
+        for (column, &factor) in column_factors.iter().enumerate() {
+            for (row, value) in &mut self.column_entries[self.column_starts[column]..self.column_starts[column + 1]] {
+                *value *= row_factors[*row] * factor;
+            }
+        }
+        for (row, &factor) in row_factors.iter().enumerate() {
+            for (column, value) in &mut self.row_entries[self.row_starts[row]..self.row_starts[row + 1]] {
+                *value *= factor * column_factors[*column];
+            }
+        }
+    }
+
+    // A copy of the matrix with the given columns added after its own
+    pub fn with_columns(&self, columns: Vec<Vec<(usize, f64)>>) -> SparseMatrix {
+        let mut all = (0..self.num_columns())
+            .map(|column| self.column(column).to_vec())
+            .collect::<Vec<_>>();
+        all.extend(columns);
+        SparseMatrix::from_columns(self.rows, all)
+    }
+
+    // A^T v for a vector indexed by row, built up from the rows where the
+    // vector is nonzero so a sparse vector only touches the rows it needs
+    pub fn transpose_product(&self, vector: &[f64]) -> Vec<f64> {
+        let mut result = vec![0.0; self.num_columns()];
+        for (row, &factor) in vector.iter().enumerate() {
+            if factor != 0.0 {
+                for &(column, value) in self.row(row) {
+                    result[column] += value * factor;
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_and_columns_agree() {
+        // [[1, 0, 2], [0, 0, 3]] given out of order with a repeated entry
+        let matrix = SparseMatrix::new(
+            2,
+            3,
+            [(1, 2, 3.0), (0, 2, 0.5), (0, 0, 1.0), (0, 2, 1.5), (1, 1, 0.0)],
+        );
+        assert_eq!(matrix.column(0), &[(0, 1.0)]);
+        assert!(matrix.column(1).is_empty());
+        assert_eq!(matrix.column(2), &[(0, 2.0), (1, 3.0)]);
+        assert_eq!(matrix.row(0), &[(0, 1.0), (2, 2.0)]);
+        assert_eq!(matrix.row(1), &[(2, 3.0)]);
+        assert_eq!(matrix.transpose_product(&[1.0, 2.0]), vec![1.0, 0.0, 8.0]);
+
+        let extended = matrix.with_columns(vec![vec![(1, 4.0)]]);
+        assert_eq!(extended.num_columns(), 4);
+        assert_eq!(extended.row(1), &[(2, 3.0), (3, 4.0)]);
+    }
+}
@@ -0,0 +1,681 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use itertools::Itertools;
+
+use crate::{
+    lp_structs::{
+        Evaluable, EvaluationContext, EvaluationError, RecursionGuard, VariableContainer,
+    },
+    Basis, Expression, LinearProgram, Operator, Pivot, SensitivityRange, SensitivityReport, Solution,
+    SolveError, SolveStatus, SolverOptions, Variable,
+};
+
+use super::{
+    postsolve::Postsolve,
+    program_transformer::{BoundConstraint, ProgramTransformer},
+    scaling::Scaling,
+    sparse_matrix::SparseMatrix,
+};
+
+// The program as the simplex method sees it: maximise costs . x + constant
+// subject to matrix x = rhs and lower <= x <= upper, where the bounds may be
+// infinite. The columns of the matrix belong to the variables in order, the
+// program's own variables followed by the slack variables of its rows
+pub struct StandardForm<'a> {
+    pub matrix: SparseMatrix,
+    pub rhs: Vec<f64>,
+    pub costs: Vec<f64>,
+    pub lower: Vec<f64>,
+    pub upper: Vec<f64>,
+    pub constant: f64,
+    pub variables: Vec<Variable<'a>>,
+    pub objective_flipped: bool,
+    pub infeasible: bool,
+    // whether presolving removed anything from the program or found it
+    // infeasible
+    pub presolved: bool,
+    // the time the solve has to stop by, counted from when the form is built
+    pub deadline: Option<Instant>,
+    substitutions: HashMap<Variable<'a>, Expression<'a>>,
+    next_temp_id: usize,
+    solved_values: HashMap<Variable<'a>, f64>,
+    // the constraint in the program each row came from, and for each column
+    // the constraints its bounds came from with the coefficient of the
+    // variable in them
+    row_origins: Vec<usize>,
+    lower_origins: Vec<Option<(usize, f64)>>,
+    upper_origins: Vec<Option<(usize, f64)>>,
+    bound_constraints: Vec<BoundConstraint<'a>>,
+    farkas: Vec<(usize, f64)>,
+    postsolve: Postsolve<'a>,
+    // the factors the rows and columns were scaled by, if they were
+    scaling: Option<Scaling>,
+    primal_tolerance: f64,
+    dual_tolerance: f64,
+}
+
+// How far each cost and right hand side can move before the optimal basis
+// changes, as intervals of changes from their values. The moves of a
+// nonbasic column are those that keep the basic variables within their
+// bounds, and a basic column cannot be moved at all
+pub struct Ranging {
+    pub costs: Vec<(f64, f64)>,
+    pub rhs: Vec<(f64, f64)>,
+    pub columns: Vec<(f64, f64)>,
+}
+
+// The result of running the simplex method on the standard form
+pub struct SimplexOutcome<'a> {
+    pub status: SolveStatus,
+    // the value of every column of the standard form
+    pub values: Vec<f64>,
+    // the dual value of every row and the reduced cost of every column,
+    // only found when the basis is optimal, or for the phase I objective
+    // when phase I shows the program to be infeasible
+    pub duals: Vec<f64>,
+    pub reduced_costs: Vec<f64>,
+    pub ranging: Option<Ranging>,
+    // the direction of every column the objective improves along without
+    // limit, only found when the program is unbounded
+    pub ray: Vec<f64>,
+    pub basis: Option<Basis<'a>>,
+    pub iterations: usize,
+    pub trace: Vec<Pivot<'a>>,
+}
+
+impl<'a> EvaluationContext<'a> for StandardForm<'a> {
+    fn evaluate(&self, var: &Variable<'a>) -> Result<f64, crate::lp_structs::EvaluationError> {
+        if let Some(value) = self.solved_values.get(var) {
+            Ok(*value)
+        } else if let Some(expr) = self.substitutions.get(var) {
+            Ok(expr.evaluate_with_recursion_guard(self, &RecursionGuard::new(*var))?)
+        } else {
+            Err(EvaluationError::UnsolvedVariable)
+        }
+    }
+
+    fn evaluate_with_recursion_guard(
+        &self,
+        var: &Variable<'a>,
+        guard: &RecursionGuard,
+    ) -> Result<f64, crate::lp_structs::EvaluationError> {
+        if guard.contains(var) {
+            Err(EvaluationError::CircularDependency)
+        } else if let Some(value) = self.solved_values.get(var) {
+            Ok(*value)
+        } else if let Some(expr) = self.substitutions.get(var) {
+            let new_guard = guard.next(*var);
+            Ok(expr.evaluate_with_recursion_guard(self, &new_guard)?)
+        } else {
+            Err(EvaluationError::UnsolvedVariable)
+        }
+    }
+}
+
+impl<'a> StandardForm<'a> {
+    pub fn new(program: &LinearProgram<'a>, options: &SolverOptions) -> StandardForm<'a> {
+        let deadline = options.time_limit.map(|limit| Instant::now() + limit);
+        let mut tf = ProgramTransformer::new(program, options);
+        tf.standardize();
+
+        // variables that only have bounds still need a column to take a value,
+        // unlike those presolving substituted away
+        let variables: Vec<Variable> = program
+            .variables_iter()
+            .chain(tf.variables_iter())
+            .unique()
+            .filter(|var| !tf.substitutions.contains_key(var))
+            .collect();
+        let index = variables
+            .iter()
+            .enumerate()
+            .map(|(column, var)| (*var, column))
+            .collect::<HashMap<_, _>>();
+
+        let index = &index;
+        let matrix = SparseMatrix::new(
+            tf.constraints.len(),
+            variables.len(),
+            tf.constraints.iter().enumerate().flat_map(|(row, constraint)| {
+                constraint
+                    .lhs
+                    .terms
+                    .iter()
+                    .map(move |term| (row, index[&term.variable], term.coefficient))
+            }),
+        );
+        let rhs = tf.constraints.iter().map(|constraint| constraint.rhs.constant).collect();
+        let (lower, upper) = variables
+            .iter()
+            .map(|var| tf.bounds.get(var).copied().unwrap_or((f64::NEG_INFINITY, f64::INFINITY)))
+            .unzip();
+
+        let lower_origins = variables.iter().map(|var| tf.lower_origins.get(var).copied()).collect();
+        let upper_origins = variables.iter().map(|var| tf.upper_origins.get(var).copied()).collect();
+
+        let mut costs = vec![0.0; variables.len()];
+        for term in tf.objective.expression.terms.iter() {
+            costs[index[&term.variable]] = term.coefficient;
+        }
+
+        let mut form = StandardForm {
+            matrix,
+            rhs,
+            costs,
+            lower,
+            upper,
+            constant: tf.objective.expression.constant,
+            variables,
+            objective_flipped: tf.objective_flipped,
+            infeasible: tf.infeasible,
+            presolved: tf.presolved,
+            deadline,
+            substitutions: tf.substitutions,
+            next_temp_id: tf.next_temp_id,
+            solved_values: HashMap::new(),
+            row_origins: tf.origins,
+            lower_origins,
+            upper_origins,
+            bound_constraints: tf.bound_constraints,
+            farkas: tf.farkas,
+            postsolve: tf.postsolve,
+            scaling: None,
+            primal_tolerance: options.primal_feasibility_tolerance,
+            dual_tolerance: options.dual_feasibility_tolerance,
+        };
+        if options.scaling {
+            form.scale();
+        }
+        form
+    }
+
+    // Scales the rows and columns of the matrix, which the right hand sides,
+    // costs and bounds follow so the program stays the same one. Scaling
+    // row i by r_i and column j by s_j turns x_j into x_j / s_j
+    fn scale(&mut self) {
+        let scaling = Scaling::new(&self.matrix);
+        self.matrix.scale(&scaling.rows, &scaling.columns);
+        for (rhs, factor) in self.rhs.iter_mut().zip(&scaling.rows) {
+            *rhs *= factor;
+        }
+        for (column, factor) in scaling.columns.iter().enumerate() {
+            self.costs[column] *= factor;
+            self.lower[column] /= factor;
+            self.upper[column] /= factor;
+        }
+        self.scaling = Some(scaling);
+    }
+
+    // Undoes the scaling on the form and on the outcome of solving it. The
+    // values and the moves of the columns grow by the column factors, while
+    // the duals grow by the row factors, and the reduced costs and the moves
+    // of the costs and right hand sides shrink by the factors they were
+    // scaled up by
+    fn unscale(&mut self, mut outcome: SimplexOutcome<'a>) -> SimplexOutcome<'a> {
+        let Some(scaling) = self.scaling.take() else {
+            return outcome;
+        };
+        let row_factors = scaling.rows.iter().map(|factor| 1.0 / factor).collect::<Vec<_>>();
+        let column_factors = scaling.columns.iter().map(|factor| 1.0 / factor).collect::<Vec<_>>();
+        self.matrix.scale(&row_factors, &column_factors);
+        for (rhs, factor) in self.rhs.iter_mut().zip(&scaling.rows) {
+            *rhs /= factor;
+        }
+        for (column, factor) in scaling.columns.iter().enumerate() {
+            self.costs[column] /= factor;
+            self.lower[column] *= factor;
+            self.upper[column] *= factor;
+        }
+
+        let grow = |values: &mut [f64], factors: &[f64]| {
+            for (value, factor) in values.iter_mut().zip(factors) {
+                *value *= factor;
+            }
+        };
+        let shrink = |ranges: &mut [(f64, f64)], factors: &[f64]| {
+            for ((lower, upper), factor) in ranges.iter_mut().zip(factors) {
+                *lower /= factor;
+                *upper /= factor;
+            }
+        };
+        grow(&mut outcome.values, &scaling.columns);
+        grow(&mut outcome.ray, &scaling.columns);
+        grow(&mut outcome.duals, &scaling.rows);
+        for (reduced_cost, factor) in outcome.reduced_costs.iter_mut().zip(&scaling.columns) {
+            *reduced_cost /= factor;
+        }
+        if let Some(ranging) = &mut outcome.ranging {
+            shrink(&mut ranging.costs, &scaling.columns);
+            shrink(&mut ranging.rhs, &scaling.rows);
+            shrink(&mut ranging.columns, &column_factors);
+        }
+        outcome
+    }
+
+    // A variable for a column the solver adds, such as an artificial variable
+    pub fn new_temp_variable(&mut self) -> Variable<'a> {
+        let variable = Variable::Temp { id: self.next_temp_id };
+        self.next_temp_id += 1;
+        variable
+    }
+
+    // The objective of the original program at the given column values
+    fn objective_value(&self, values: &[f64]) -> f64 {
+        let value = self.costs.iter().zip(values).map(|(cost, value)| cost * value).sum::<f64>()
+            + self.constant;
+        if self.objective_flipped {
+            -value
+        } else {
+            value
+        }
+    }
+
+    // The multiplier of every constraint of the program given by the dual
+    // values of the rows and the reduced costs of the columns, along with the
+    // reduced costs of the variables presolving fixed. A row passes on its
+    // dual value to the constraint it came from, and a nonbasic variable held
+    // at a bound passes on its reduced cost to the constraint that gave the
+    // bound, divided by the variable's coefficient. The rest are found by
+    // undoing the reductions presolving made
+    fn constraint_multipliers(
+        &self,
+        program: &LinearProgram<'a>,
+        values: &[f64],
+        duals: &[f64],
+        reduced_costs: &[f64],
+    ) -> (Vec<f64>, HashMap<Variable<'a>, f64>) {
+        let mut multipliers = vec![0.0; program.constraints.len()];
+        for (&origin, &dual) in self.row_origins.iter().zip(duals) {
+            multipliers[origin] = dual;
+        }
+        for (column, &reduced_cost) in reduced_costs.iter().enumerate() {
+            let origin = if reduced_cost > self.dual_tolerance
+                && values[column] >= self.upper[column] - self.primal_tolerance
+            {
+                self.upper_origins[column]
+            } else if reduced_cost < -self.dual_tolerance
+                && values[column] <= self.lower[column] + self.primal_tolerance
+            {
+                self.lower_origins[column]
+            } else {
+                None
+            };
+            if let Some((constraint, coefficient)) = origin {
+                multipliers[constraint] = reduced_cost / coefficient;
+            }
+        }
+        let fixed = self.postsolve.restore_multipliers(&mut multipliers, self.dual_tolerance);
+        (multipliers, fixed)
+    }
+
+    // The shadow price of every constraint of the program and the reduced
+    // cost of every named variable, in terms of the program's own objective
+    fn sensitivities(
+        &self,
+        program: &LinearProgram<'a>,
+        values: &[f64],
+        duals: &[f64],
+        reduced_costs: &[f64],
+    ) -> (Vec<f64>, HashMap<Variable<'a>, f64>) {
+        let sign = if self.objective_flipped { -1.0 } else { 1.0 };
+        let (multipliers, fixed) = self.constraint_multipliers(program, values, duals, reduced_costs);
+        let shadow_prices = multipliers.into_iter().map(|multiplier| sign * multiplier).collect();
+
+        let index = self
+            .variables
+            .iter()
+            .enumerate()
+            .map(|(column, var)| (*var, column))
+            .collect::<HashMap<_, _>>();
+        // variables without a column that presolving did not fix have no cost
+        // and no rows to affect
+        let reduced_costs = program
+            .variables_iter()
+            .filter(|var| matches!(var, Variable::Named { .. }))
+            .map(|var| {
+                let reduced_cost = match index.get(&var) {
+                    Some(&column) => reduced_costs[column],
+                    None => fixed.get(&var).copied().unwrap_or(0.0),
+                };
+                (var, sign * reduced_cost)
+            })
+            .collect();
+        (shadow_prices, reduced_costs)
+    }
+
+    // The direction of every named variable along the ray found on the
+    // standard form. A variable substituted away moves as the terms of the
+    // expression that replaced it do, leaving out its constant, and a
+    // variable that was eliminated from every row does not move at all
+    fn ray_components(&self, program: &LinearProgram<'a>, ray: &[f64]) -> HashMap<Variable<'a>, f64> {
+        fn component<'a>(
+            var: &Variable<'a>,
+            columns: &HashMap<Variable<'a>, f64>,
+            substitutions: &HashMap<Variable<'a>, Expression<'a>>,
+        ) -> f64 {
+            if let Some(&value) = columns.get(var) {
+                value
+            } else if let Some(expr) = substitutions.get(var) {
+                expr.terms
+                    .iter()
+                    .map(|term| term.coefficient * component(&term.variable, columns, substitutions))
+                    .sum()
+            } else {
+                0.0
+            }
+        }
+
+        let columns = self.variables.iter().copied().zip(ray.iter().copied()).collect();
+        program
+            .variables_iter()
+            .filter(|var| matches!(var, Variable::Named { .. }))
+            .map(|var| (var, component(&var, &columns, &self.substitutions)))
+            .collect()
+    }
+
+    // A Farkas certificate of infeasibility, one multiplier per constraint of
+    // the program. At the end of phase I every column's cost of zero is made
+    // up of its reduced cost and the dual values of its rows, so the
+    // multipliers found from them cancel every variable, while what they make
+    // of the right hand sides is the sum of the artificial variables left,
+    // which is negative. Nothing is given if round-off spoils that
+    fn farkas_certificate(
+        &self,
+        program: &LinearProgram<'a>,
+        values: &[f64],
+        duals: &[f64],
+        reduced_costs: &[f64],
+    ) -> Vec<f64> {
+        let certificate = if !self.farkas.is_empty() {
+            let mut certificate = vec![0.0; program.constraints.len()];
+            for &(origin, multiplier) in &self.farkas {
+                certificate[origin] = multiplier;
+            }
+            certificate
+        } else if !duals.is_empty() {
+            self.constraint_multipliers(program, values, duals, reduced_costs).0
+        } else {
+            return vec![];
+        };
+        let bound = program
+            .constraints
+            .iter()
+            .zip(&certificate)
+            .map(|(c, multiplier)| multiplier * (c.rhs.constant - c.lhs.constant))
+            .sum::<f64>();
+        if bound < -self.primal_tolerance {
+            certificate
+        } else {
+            vec![]
+        }
+    }
+
+    // The changes to the bounds of a column that keep the basis optimal,
+    // along with whether each bound is holding the variable. A nonbasic
+    // variable moves along with the bound it sits at, as long as its reduced
+    // cost lets it sit there, and can take it up to its other bound. Any
+    // other bound can move until it reaches the value of the variable
+    fn bound_ranges(
+        &self,
+        column: usize,
+        value: f64,
+        reduced_cost: f64,
+        is_basic: bool,
+        moves: (f64, f64),
+    ) -> [((f64, f64), bool); 2] {
+        let (lower, upper) = (self.lower[column], self.upper[column]);
+        let (primal, dual) = (self.primal_tolerance, self.dual_tolerance);
+        let holds_lower = !is_basic && value <= lower + primal && reduced_cost <= dual;
+        let holds_upper = !is_basic && value >= upper - primal && reduced_cost >= -dual;
+        let lower_range = if holds_lower {
+            (moves.0, moves.1.min(upper - lower))
+        } else {
+            (f64::NEG_INFINITY, value - lower)
+        };
+        let upper_range = if holds_upper {
+            (moves.0.max(lower - upper), moves.1)
+        } else {
+            (value - upper, f64::INFINITY)
+        };
+        [(lower_range, holds_lower), (upper_range, holds_upper)]
+    }
+
+    // Maps the ranges found on the standard form back to the program. A row
+    // keeps the right hand side of the constraint it came from, and a
+    // constraint on a single variable moves the bound it gave, scaled by the
+    // variable's coefficient. The ranges end where another constraint on the
+    // same variable would take over giving the bound, as the shadow price
+    // changes there even though the basis does not
+    fn sensitivity_report(
+        &self,
+        program: &LinearProgram<'a>,
+        values: &[f64],
+        reduced_costs: &[f64],
+        basis: &Basis<'a>,
+        ranging: &Ranging,
+    ) -> SensitivityReport<'a> {
+        let index = self
+            .variables
+            .iter()
+            .enumerate()
+            .map(|(column, var)| (*var, column))
+            .collect::<HashMap<_, _>>();
+        let mut is_basic = vec![false; self.variables.len()];
+        for var in &basis.variables {
+            is_basic[index[var]] = true;
+        }
+        let sign = if self.objective_flipped { -1.0 } else { 1.0 };
+        let scale = |(lower, upper): (f64, f64), factor: f64| {
+            if factor > 0.0 {
+                (lower * factor, upper * factor)
+            } else {
+                (upper * factor, lower * factor)
+            }
+        };
+
+        let objective_coefficients = program
+            .variables_iter()
+            .filter(|var| matches!(var, Variable::Named { .. }))
+            .map(|var| {
+                let coefficient = program
+                    .objective
+                    .expression
+                    .terms
+                    .iter()
+                    .filter(|term| term.variable == var)
+                    .map(|term| term.coefficient)
+                    .sum::<f64>();
+                let (lower, upper) = index
+                    .get(&var)
+                    .map_or((0.0, 0.0), |&column| scale(ranging.costs[column], sign));
+                (var, SensitivityRange { lower: coefficient + lower, upper: coefficient + upper })
+            })
+            .collect();
+
+        let mut changes = program
+            .constraints
+            .iter()
+            .map(|c| {
+                // a constraint without any variables holds while its sides keep their order
+                let difference = c.rhs.constant - c.lhs.constant;
+                match c.operator {
+                    Operator::LTE => (-difference, f64::INFINITY),
+                    Operator::GTE => (f64::NEG_INFINITY, -difference),
+                    Operator::Eq => (0.0, 0.0),
+                }
+            })
+            .collect::<Vec<_>>();
+        for (&origin, &range) in self.row_origins.iter().zip(&ranging.rhs) {
+            changes[origin] = range;
+        }
+        for bound in &self.bound_constraints {
+            let column = index[&bound.variable];
+            let [(lower_range, holds_lower), (upper_range, holds_upper)] = self.bound_ranges(
+                column,
+                values[column],
+                reduced_costs[column],
+                is_basic[column],
+                ranging.columns[column],
+            );
+            let (lower, upper) = (self.lower[column], self.upper[column]);
+            let others = self
+                .bound_constraints
+                .iter()
+                .filter(|other| other.variable == bound.variable && other.origin != bound.origin);
+            let next_lower = others
+                .clone()
+                .filter(|other| other.operator != Operator::LTE)
+                .map(|other| other.value)
+                .fold(f64::NEG_INFINITY, f64::max);
+            let next_upper = others
+                .filter(|other| other.operator != Operator::GTE)
+                .map(|other| other.value)
+                .fold(f64::INFINITY, f64::min);
+            let gives_lower = self.lower_origins[column].map(|(origin, _)| origin) == Some(bound.origin);
+            let gives_upper = self.upper_origins[column].map(|(origin, _)| origin) == Some(bound.origin);
+
+            let range = match bound.operator {
+                Operator::GTE if gives_lower => {
+                    let (low, high) = lower_range;
+                    (low.max(next_lower - lower), high)
+                }
+                Operator::GTE if holds_lower => (f64::NEG_INFINITY, lower - bound.value),
+                Operator::GTE => (f64::NEG_INFINITY, lower - bound.value + lower_range.1),
+                Operator::LTE if gives_upper => {
+                    let (low, high) = upper_range;
+                    (low, high.min(next_upper - upper))
+                }
+                Operator::LTE if holds_upper => (upper - bound.value, f64::INFINITY),
+                Operator::LTE => (upper - bound.value + upper_range.0, f64::INFINITY),
+                Operator::Eq if gives_lower && gives_upper && !is_basic[column] => {
+                    let (low, high) = ranging.columns[column];
+                    (low.max(next_lower - bound.value), high.min(next_upper - bound.value))
+                }
+                Operator::Eq => (0.0, 0.0),
+            };
+            changes[bound.origin] = scale(range, bound.coefficient);
+        }
+
+        let right_hand_sides = program
+            .constraints
+            .iter()
+            .zip(changes)
+            .map(|(c, (lower, upper))| SensitivityRange {
+                lower: c.rhs.constant + lower,
+                upper: c.rhs.constant + upper,
+            })
+            .collect();
+        SensitivityReport { objective_coefficients, right_hand_sides }
+    }
+
+    pub fn into_solution(
+        mut self,
+        program: &LinearProgram<'a>,
+        outcome: SimplexOutcome<'a>,
+    ) -> Result<Solution<'a>, SolveError> {
+        let outcome = self.unscale(outcome);
+        let SimplexOutcome {
+            status,
+            values,
+            duals,
+            reduced_costs,
+            ranging,
+            ray,
+            basis,
+            iterations,
+            trace,
+        } = outcome;
+        if status == SolveStatus::Infeasible {
+            let farkas_certificate = self.farkas_certificate(program, &values, &duals, &reduced_costs);
+            return Ok(Solution {
+                status,
+                objective_value: f64::NAN,
+                values: HashMap::new(),
+                shadow_prices: vec![],
+                reduced_costs: HashMap::new(),
+                slacks: vec![],
+                sensitivity: None,
+                farkas_certificate,
+                unbounded_ray: HashMap::new(),
+                iterations,
+                trace,
+                basis,
+            });
+        }
+
+        for (variable, &value) in self.variables.iter().zip(&values) {
+            self.solved_values.insert(*variable, value);
+        }
+
+        // variables that were eliminated from every row are free to sit at zero
+        let eliminated = self.substitutions
+            .values()
+            .flat_map(|expr| expr.variables_iter())
+            .chain(program.variables_iter())
+            .filter(|var| !self.substitutions.contains_key(var))
+            .collect::<Vec<_>>();
+        for variable in eliminated {
+            self.solved_values.entry(variable).or_insert(0.0);
+        }
+
+        let objective_value = match status {
+            SolveStatus::Unbounded if self.objective_flipped => f64::NEG_INFINITY,
+            SolveStatus::Unbounded => f64::INFINITY,
+            _ => self.objective_value(&values),
+        };
+
+        // the ranges of what presolving removed are not known
+        let sensitivity = match (&basis, &ranging) {
+            (Some(basis), Some(ranging)) if !self.presolved => {
+                Some(self.sensitivity_report(program, &values, &reduced_costs, basis, ranging))
+            }
+            _ => None,
+        };
+        let (shadow_prices, reduced_costs) = if status == SolveStatus::Optimal {
+            self.sensitivities(program, &values, &duals, &reduced_costs)
+        } else {
+            (vec![], HashMap::new())
+        };
+
+        // how far each constraint is from holding with equality, measured
+        // in the direction it allows
+        let slacks = program
+            .constraints
+            .iter()
+            .map(|c| {
+                let difference = c.rhs.evaluate(&self)? - c.lhs.evaluate(&self)?;
+                Ok(match c.operator {
+                    Operator::GTE => -difference,
+                    Operator::LTE | Operator::Eq => difference,
+                })
+            })
+            .collect::<Result<_, EvaluationError>>()?;
+
+        let unbounded_ray = if ray.is_empty() {
+            HashMap::new()
+        } else {
+            self.ray_components(program, &ray)
+        };
+
+        let values = program.variables_iter()
+            .filter(|var| matches!(var, Variable::Named { .. }))
+            .map(|var| Ok((var, self.evaluate(&var)?)))
+            .collect::<Result<_, EvaluationError>>()?;
+
+        Ok(Solution {
+            status,
+            objective_value,
+            values,
+            shadow_prices,
+            reduced_costs,
+            slacks,
+            sensitivity,
+            farkas_certificate: vec![],
+            unbounded_ray,
+            iterations,
+            trace,
+            basis,
+        })
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::lp_structs::RecursionGuard;
+use crate::{EvaluationContext, EvaluationError, SensitivityReport, Variable};
+
+// Represents the state the solver finished in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SolveStatus {
+    Optimal,
+    Infeasible,
+    Unbounded,
+    IterationLimit,
+    TimeLimit,
+    NodeLimit,
+    NumericalTrouble,
+}
+
+impl Display for SolveStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveStatus::Optimal => write!(f, "optimal"),
+            SolveStatus::Infeasible => write!(f, "infeasible"),
+            SolveStatus::Unbounded => write!(f, "unbounded"),
+            SolveStatus::IterationLimit => write!(f, "iteration limit reached"),
+            SolveStatus::TimeLimit => write!(f, "time limit reached"),
+            SolveStatus::NodeLimit => write!(f, "node limit reached"),
+            SolveStatus::NumericalTrouble => write!(f, "numerical trouble"),
+        }
+    }
+}
+
+// Represents which objective the simplex method was optimising when it pivoted,
+// the sum of the artificial variables in phase I or the program's own
+// objective in phase II
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Phase {
+    One,
+    Two,
+}
+
+// Represents a single pivot of the simplex method along with the value of the
+// objective being optimised once the pivot was made. A variable moving from
+// one of its bounds to the other without a change of basis is recorded as
+// both entering and leaving
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pivot<'a> {
+    pub phase: Phase,
+    pub entering: Variable<'a>,
+    pub leaving: Variable<'a>,
+    pub objective_value: f64,
+}
+
+impl Display for Pivot<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let phase = match self.phase {
+            Phase::One => "I",
+            Phase::Two => "II",
+        };
+        write!(
+            f,
+            "phase {}: {} enters, {} leaves, objective {}",
+            phase, self.entering, self.leaving, self.objective_value
+        )
+    }
+}
+
+// Represents the basis the simplex method finished with, as the variables of
+// the standardized program that were basic. The standardized program only
+// grows at the end when constraints are added, so the basis carries over to a
+// program with added constraints or changed right hand sides
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Basis<'a> {
+    pub variables: Vec<Variable<'a>>,
+}
+
+// Represents the result of solving a linear program
+#[derive(Clone, Debug)]
+pub struct Solution<'a> {
+    pub status: SolveStatus,
+    // Infinite when the program is unbounded and NaN when it is infeasible
+    pub objective_value: f64,
+    // The last vertex visited when the solver stopped early or the program is
+    // unbounded, and empty when it is infeasible
+    pub values: HashMap<Variable<'a>, f64>,
+    // Per constraint in order, the rate at which the objective value changes
+    // as its right hand side grows. Only given for optimal solutions
+    pub shadow_prices: Vec<f64>,
+    // The rate at which the objective value changes as the variable moves.
+    // A single variable constraint holding it at a bound takes this on
+    pub reduced_costs: HashMap<Variable<'a>, f64>,
+    // Per constraint in order, how far it is from holding with equality,
+    // negative when violated. Given whenever there are values
+    pub slacks: Vec<f64>,
+    // The ranging of costs and right hand sides, only for optimal solutions
+    pub sensitivity: Option<SensitivityReport<'a>>,
+    // Per constraint multipliers proving infeasibility, nonnegative for <= and
+    // nonpositive for >=, whose sum leaves 0 <= a negative number. Empty when
+    // round-off keeps the solver from finding one
+    pub farkas_certificate: Vec<f64>,
+    // The direction each variable moves in from its value along which the
+    // constraints keep holding and an unbounded objective improves forever
+    pub unbounded_ray: HashMap<Variable<'a>, f64>,
+    pub iterations: usize,
+    // Every pivot made, only filled in when requested in the solver options
+    pub trace: Vec<Pivot<'a>>,
+    // Only given when an optimal basis was found
+    pub basis: Option<Basis<'a>>,
+}
+
+impl<'a> Solution<'a> {
+    pub fn value(&self, variable: &Variable<'a>) -> Option<f64> {
+        self.values.get(variable).copied()
+    }
+
+    pub fn reduced_cost(&self, variable: &Variable<'a>) -> Option<f64> {
+        self.reduced_costs.get(variable).copied()
+    }
+}
+
+impl<'a> EvaluationContext<'a> for Solution<'a> {
+    fn evaluate(&self, var: &Variable<'a>) -> Result<f64, EvaluationError> {
+        self.value(var).ok_or(EvaluationError::UnsolvedVariable)
+    }
+
+    fn evaluate_with_recursion_guard(
+        &self,
+        var: &Variable<'a>,
+        _guard: &RecursionGuard,
+    ) -> Result<f64, EvaluationError> {
+        self.evaluate(var)
+    }
+}
+
+impl Display for Solution<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}: {}", self.status, self.objective_value)?;
+        let mut values = self.values.iter().collect::<Vec<_>>();
+        values.sort_by_key(|(variable, _)| variable.to_string());
+        for (variable, value) in values {
+            writeln!(f, "{} = {}", variable, value)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt::Display;
+
+use crate::EvaluationError;
+
+// Represents a linear program the solver cannot work with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    // A coefficient or constant in the objective is NaN or infinite
+    NonFiniteObjective,
+    // A coefficient or constant in the constraint at this index is NaN or infinite
+    NonFiniteConstraint { index: usize },
+    // A variable's value could not be recovered from the substitutions made
+    // while standardizing the program
+    Evaluation(EvaluationError),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::NonFiniteObjective => {
+                write!(f, "the objective contains a value that is not finite")
+            }
+            SolveError::NonFiniteConstraint { index } => {
+                write!(f, "constraint {} contains a value that is not finite", index)
+            }
+            SolveError::Evaluation(error) => {
+                write!(f, "could not recover the solution: {}", error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SolveError::Evaluation(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<EvaluationError> for SolveError {
+    fn from(error: EvaluationError) -> Self {
+        SolveError::Evaluation(error)
+    }
+}
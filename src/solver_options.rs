@@ -0,0 +1,149 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use crate::PivotRule;
+
+// Represents how the simplex method finds a feasible basis to start from
+// when the constraints do not provide one
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InitialBasis {
+    // Minimise the sum of the artificial variables first, then optimise
+    // the objective from the feasible basis found
+    TwoPhase,
+    // Optimise the objective with every artificial variable penalised by M.
+    // When no M is given one is derived from the coefficients of the program
+    BigM(Option<f64>),
+}
+
+// Represents which form of the simplex method is run
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    // Keep the basis feasible and improve the objective with every pivot
+    PrimalSimplex,
+    // Keep the reduced costs optimal and restore feasibility with every
+    // pivot, starting from the slack basis when it is dual feasible and
+    // falling back to the primal method when it is not
+    DualSimplex,
+    // Mehrotra's primal-dual interior point method, which moves through the
+    // inside of the feasible region in few iterations however degenerate the
+    // program is. The optimum found has no basis until it is crossed over to
+    // one, and a warm start basis is not used. The simplex method decides
+    // programs the interior point method cannot solve, such as infeasible or
+    // unbounded ones
+    InteriorPoint,
+}
+
+// Represents how the simplex method keeps track of the basis
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimplexMethod {
+    // Pivot the full tableau, which is simple and exact to follow but
+    // touches every entry on every pivot
+    Tableau,
+    // Keep an LU factorization of the basis, updated in product form and
+    // refactorized periodically, which scales to larger sparse programs
+    Revised,
+}
+
+// Represents which node branch and bound explores next
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NodeSelection {
+    // The node whose relaxation has the best objective value, which proves
+    // the optimum with the fewest nodes
+    BestBound,
+    // The node most recently branched on, diving towards an integer solution
+    // with few nodes kept in memory
+    DepthFirst,
+}
+
+impl Display for NodeSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeSelection::BestBound => write!(f, "best bound"),
+            NodeSelection::DepthFirst => write!(f, "depth first"),
+        }
+    }
+}
+
+// Represents the settings the solver runs with
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolverOptions {
+    pub algorithm: Algorithm,
+    pub simplex_method: SimplexMethod,
+    pub initial_basis: InitialBasis,
+    pub pivot_rule: PivotRule,
+    // Keep every pivot in the solution, and the progress of branch and bound
+    // every time its best integer solution or best bound moves
+    pub record_trace: bool,
+    // Move the interior point optimum to an optimal basis with a few simplex
+    // pivots, giving it a basis to warm start from and a sensitivity report
+    pub crossover: bool,
+    // Remove fixed and empty variables, singleton rows, duplicate and
+    // redundant constraints before solving, and undo it on the solution
+    pub presolve: bool,
+    // Multiply rows and columns by powers of two to bring coefficients close
+    // to one in size. The tolerances apply to the scaled program
+    pub scaling: bool,
+    // How far a basic variable may lie outside its bounds
+    pub primal_feasibility_tolerance: f64,
+    // How far a reduced cost may have the wrong sign
+    pub dual_feasibility_tolerance: f64,
+    // Entries no larger than this are never pivoted on
+    pub pivot_tolerance: f64,
+    // Coefficients no larger than this are dropped from the program, so
+    // terms that fail to cancel through round-off do not become rows
+    pub zero_tolerance: f64,
+    // The number of pivots or interior point iterations after which to stop
+    pub iteration_limit: usize,
+    // The time after which to stop, covering the whole branch and bound
+    // search while every relaxation is given the time left
+    pub time_limit: Option<Duration>,
+    pub node_selection: NodeSelection,
+    // How close to an integer a value has to be to count as that integer
+    pub integrality_tolerance: f64,
+    // The rounds of Gomory cuts added at the root before branching, which
+    // the cutting plane method keeps adding in place of branching
+    pub cut_rounds: usize,
+    // Round the optimum of every relaxation that is branched on
+    pub rounding: bool,
+    // Dive from the root optimum by fixing integer variables one at a time
+    pub diving: bool,
+    // Pump the root optimum towards an integer solution when none is known
+    pub feasibility_pump: bool,
+    // Stop once the best bound is within this fraction of the best integer
+    // solution's objective value, or of one when that is smaller in size
+    pub relative_gap: f64,
+    // Stop once the best bound is within this of the best integer solution
+    pub absolute_gap: f64,
+    // The number of branch and bound nodes after which to stop
+    pub node_limit: Option<usize>,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        SolverOptions {
+            algorithm: Algorithm::PrimalSimplex,
+            simplex_method: SimplexMethod::Tableau,
+            initial_basis: InitialBasis::TwoPhase,
+            pivot_rule: PivotRule::Dantzig,
+            record_trace: false,
+            crossover: true,
+            presolve: false,
+            scaling: false,
+            primal_feasibility_tolerance: 1e-7,
+            dual_feasibility_tolerance: 1e-9,
+            pivot_tolerance: 1e-9,
+            zero_tolerance: 1e-12,
+            iteration_limit: 50_000,
+            time_limit: None,
+            node_selection: NodeSelection::BestBound,
+            integrality_tolerance: 1e-6,
+            cut_rounds: 10,
+            rounding: true,
+            diving: true,
+            feasibility_pump: true,
+            relative_gap: 0.0,
+            absolute_gap: 0.0,
+            node_limit: None,
+        }
+    }
+}